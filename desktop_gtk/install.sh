@@ -20,16 +20,20 @@ cat >"$DESKTOP_DIR/$APP_ID.desktop" <<EOF
 [Desktop Entry]
 Name=DLM
 Comment=Download Manager desktop client
-Exec=$BIN_DIR/$APP_NAME
+Exec=$BIN_DIR/$APP_NAME %u
 Icon=folder-download-symbolic
 Terminal=false
 Type=Application
 Categories=Network;FileTransfer;
+MimeType=x-scheme-handler/dlm;
 EOF
 
 echo "Updating desktop database..."
 update-desktop-database "$DESKTOP_DIR" 2>/dev/null || true
 
+echo "Registering dlm:// link handler..."
+xdg-mime default "$APP_ID.desktop" x-scheme-handler/dlm 2>/dev/null || true
+
 echo ""
 echo "Installation complete!"
 echo "  Binary: $BIN_DIR/$APP_NAME"
@@ -1,181 +1,267 @@
+use std::time::Duration;
+
 use crate::types::*;
 
-const USER_AGENT: &str = concat!("dlm-gtk/", env!("CARGO_PKG_VERSION"));
+pub const USER_AGENT: &str = concat!("dlm-gtk/", env!("CARGO_PKG_VERSION"));
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
 
 fn agent() -> ureq::Agent {
-    ureq::AgentBuilder::new().user_agent(USER_AGENT).build()
+    ureq::AgentBuilder::new()
+        .user_agent(USER_AGENT)
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+}
+
+/// Errors returned by API calls
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApiError {
+    /// The server could not be reached (connection refused, DNS, TLS, ...)
+    Transport(String),
+    /// The server answered with a non-2xx status and an optional message body
+    Status { code: u16, message: String },
+    /// The response body was not the JSON we expected
+    Decode(String),
+    /// The request did not complete within `REQUEST_TIMEOUT`
+    Timeout,
+}
+
+impl ApiError {
+    /// Build a status error from the response body, preferring the server's
+    /// `message` (or `error`) field over the raw text
+    pub fn from_status(code: u16, body: &str) -> Self {
+        let message = serde_json::from_str::<ErrorResponse>(body)
+            .ok()
+            .and_then(|r| r.message.or(r.error))
+            .unwrap_or_else(|| body.trim().to_string());
+        ApiError::Status { code, message }
+    }
+
+    /// Whether repeating the same request might succeed
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            ApiError::Transport(_) | ApiError::Timeout => true,
+            ApiError::Status { code, .. } => *code >= 500,
+            ApiError::Decode(_) => false,
+        }
+    }
+}
+
+impl std::fmt::Display for ApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ApiError::Transport(e) => write!(f, "Server unreachable: {e}"),
+            ApiError::Status { code, message } if message.is_empty() => {
+                write!(f, "Server returned HTTP {code}")
+            }
+            ApiError::Status { message, .. } => {
+                let mut chars = message.chars();
+                match chars.next() {
+                    Some(first) => write!(f, "{}{}", first.to_uppercase(), chars.as_str()),
+                    None => Ok(()),
+                }
+            }
+            ApiError::Decode(e) => write!(f, "Unexpected response from server: {e}"),
+            ApiError::Timeout => write!(f, "Server took too long to respond"),
+        }
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+impl From<ureq::Error> for ApiError {
+    fn from(e: ureq::Error) -> Self {
+        match e {
+            ureq::Error::Status(code, resp) => {
+                let body = resp.into_string().unwrap_or_default();
+                ApiError::from_status(code, &body)
+            }
+            ureq::Error::Transport(t) => {
+                let timed_out = std::error::Error::source(&t)
+                    .and_then(|s| s.downcast_ref::<std::io::Error>())
+                    .is_some_and(|io| io.kind() == std::io::ErrorKind::TimedOut);
+                if timed_out {
+                    ApiError::Timeout
+                } else {
+                    ApiError::Transport(t.to_string())
+                }
+            }
+        }
+    }
+}
+
+impl From<std::io::Error> for ApiError {
+    fn from(e: std::io::Error) -> Self {
+        if e.kind() == std::io::ErrorKind::TimedOut {
+            ApiError::Timeout
+        } else {
+            ApiError::Decode(e.to_string())
+        }
+    }
 }
 
 /// Fetch status counts from /api/count
-pub fn fetch_counts(api_url: &str) -> Result<Vec<StatusCount>, String> {
+pub fn fetch_counts(api_url: &str) -> Result<Vec<StatusCount>, ApiError> {
     let resp: CountResponse = agent()
         .get(&format!("{api_url}/api/count"))
-        .call()
-        .map_err(|e| e.to_string())?
-        .into_json()
-        .map_err(|e| e.to_string())?;
+        .call()?
+        .into_json()?;
     Ok(resp.status_groups)
 }
 
 /// Fetch all downloads from /api/downloads
-pub fn fetch_downloads(api_url: &str) -> Result<Vec<Download>, String> {
+pub fn fetch_downloads(api_url: &str) -> Result<Vec<Download>, ApiError> {
     let resp: DownloadsResponse = agent()
         .get(&format!("{api_url}/api/downloads"))
-        .call()
-        .map_err(|e| e.to_string())?
-        .into_json()
-        .map_err(|e| e.to_string())?;
+        .call()?
+        .into_json()?;
     Ok(resp.downloads)
 }
 
 /// Fetch system info from /api/system
-pub fn fetch_system(api_url: &str) -> Result<SystemInfo, String> {
+pub fn fetch_system(api_url: &str) -> Result<SystemInfo, ApiError> {
     let resp: SystemInfo = agent()
         .get(&format!("{api_url}/api/system"))
-        .call()
-        .map_err(|e| e.to_string())?
-        .into_json()
-        .map_err(|e| e.to_string())?;
+        .call()?
+        .into_json()?;
     Ok(resp)
 }
 
 /// Fetch logs from /api/logs
-pub fn fetch_logs(api_url: &str) -> Result<Vec<String>, String> {
+pub fn fetch_logs(api_url: &str) -> Result<Vec<String>, ApiError> {
     let resp: LogsResponse = agent()
         .get(&format!("{api_url}/api/logs"))
-        .call()
-        .map_err(|e| e.to_string())?
-        .into_json()
-        .map_err(|e| e.to_string())?;
+        .call()?
+        .into_json()?;
     Ok(resp.logs)
 }
 
 /// Fetch config from /api/config
-pub fn fetch_config(api_url: &str) -> Result<ConfigResponse, String> {
+pub fn fetch_config(api_url: &str) -> Result<ConfigResponse, ApiError> {
     let resp: ConfigResponse = agent()
         .get(&format!("{api_url}/api/config"))
-        .call()
-        .map_err(|e| e.to_string())?
-        .into_json()
-        .map_err(|e| e.to_string())?;
+        .call()?
+        .into_json()?;
     Ok(resp)
 }
 
-/// Fetch all data in one call (for refresh)
+/// Fetch all data in one call (for refresh), recording which endpoints failed
 pub fn fetch_all(api_url: &str) -> RefreshData {
-    let counts = fetch_counts(api_url).unwrap_or_default();
-    let downloads = fetch_downloads(api_url).unwrap_or_default();
-    let system = fetch_system(api_url).ok();
-    let logs = fetch_logs(api_url).unwrap_or_default();
-    let config = fetch_config(api_url).ok();
-
-    RefreshData {
-        counts,
-        downloads,
-        system,
-        logs,
-        config,
+    let mut data = RefreshData::default();
+    match fetch_counts(api_url) {
+        Ok(counts) => data.counts = counts,
+        // The remaining endpoints would fail the same way, so don't wait on each
+        Err(e @ (ApiError::Transport(_) | ApiError::Timeout)) => return RefreshData::failed(e),
+        Err(e) => data.errors.push((Endpoint::Counts, e)),
     }
+
+    let errors = &mut data.errors;
+    data.downloads =
+        record(errors, Endpoint::Downloads, fetch_downloads(api_url)).unwrap_or_default();
+    data.system = record(errors, Endpoint::System, fetch_system(api_url));
+    data.logs = record(errors, Endpoint::Logs, fetch_logs(api_url)).unwrap_or_default();
+    data.config = record(errors, Endpoint::Config, fetch_config(api_url));
+    data
+}
+
+/// Push a failed result onto `errors`, returning the value on success
+fn record<T>(
+    errors: &mut Vec<(Endpoint, ApiError)>,
+    endpoint: Endpoint,
+    result: Result<T, ApiError>,
+) -> Option<T> {
+    result.map_err(|e| errors.push((endpoint, e))).ok()
 }
 
 /// Add URLs to download queue
-pub fn add_urls(api_url: &str, urls: &[String]) -> Result<String, String> {
+pub fn add_urls(api_url: &str, urls: &[String]) -> Result<String, ApiError> {
     let body = serde_json::json!({ "urls": urls });
     let resp: MessageResponse = agent()
         .post(&format!("{api_url}/api/add-urls"))
-        .send_json(body)
-        .map_err(|e| e.to_string())?
-        .into_json()
-        .map_err(|e| e.to_string())?;
+        .send_json(body)?
+        .into_json()?;
     Ok(resp.message)
 }
 
 /// Start downloads with a limit
-pub fn start_downloads(api_url: &str, limit: i32) -> Result<String, String> {
+pub fn start_downloads(api_url: &str, limit: i32) -> Result<String, ApiError> {
     let body = serde_json::json!({ "limit": limit });
     let resp: MessageResponse = agent()
         .post(&format!("{api_url}/api/download"))
-        .send_json(body)
-        .map_err(|e| e.to_string())?
-        .into_json()
-        .map_err(|e| e.to_string())?;
+        .send_json(body)?
+        .into_json()?;
     Ok(resp.message)
 }
 
 /// Retry a single failed download
-pub fn retry_download(api_url: &str, id: i64) -> Result<String, String> {
+pub fn retry_download(api_url: &str, id: i64) -> Result<String, ApiError> {
     let resp: MessageResponse = agent()
         .post(&format!("{api_url}/api/retry/{id}"))
-        .call()
-        .map_err(|e| e.to_string())?
-        .into_json()
-        .map_err(|e| e.to_string())?;
+        .call()?
+        .into_json()?;
     Ok(resp.message)
 }
 
 /// Retry all failed downloads
-pub fn retry_all_failed(api_url: &str) -> Result<String, String> {
+pub fn retry_all_failed(api_url: &str) -> Result<String, ApiError> {
     let resp: MessageResponse = agent()
         .post(&format!("{api_url}/api/retry-all-failed"))
-        .call()
-        .map_err(|e| e.to_string())?
-        .into_json()
-        .map_err(|e| e.to_string())?;
+        .call()?
+        .into_json()?;
     Ok(resp.message)
 }
 
 /// Delete a single download
-pub fn delete_download(api_url: &str, id: i64) -> Result<String, String> {
+pub fn delete_download(api_url: &str, id: i64) -> Result<String, ApiError> {
     let resp: MessageResponse = agent()
         .delete(&format!("{api_url}/api/download/{id}"))
-        .call()
-        .map_err(|e| e.to_string())?
-        .into_json()
-        .map_err(|e| e.to_string())?;
+        .call()?
+        .into_json()?;
     Ok(resp.message)
 }
 
 /// Delete all failed downloads
-pub fn delete_all_failed(api_url: &str) -> Result<String, String> {
+pub fn delete_all_failed(api_url: &str) -> Result<String, ApiError> {
     let resp: MessageResponse = agent()
         .delete(&format!("{api_url}/api/delete-all-failed"))
-        .call()
-        .map_err(|e| e.to_string())?
-        .into_json()
-        .map_err(|e| e.to_string())?;
+        .call()?
+        .into_json()?;
     Ok(resp.message)
 }
 
 /// Redownload a successful download
-pub fn redownload(api_url: &str, id: i64) -> Result<String, String> {
+pub fn redownload(api_url: &str, id: i64) -> Result<String, ApiError> {
     let resp: MessageResponse = agent()
         .post(&format!("{api_url}/api/redownload/{id}"))
-        .call()
-        .map_err(|e| e.to_string())?
-        .into_json()
-        .map_err(|e| e.to_string())?;
+        .call()?
+        .into_json()?;
     Ok(resp.message)
 }
 
 /// Reset a stuck downloading item to pending
-pub fn reset_download(api_url: &str, id: i64) -> Result<String, String> {
+pub fn reset_download(api_url: &str, id: i64) -> Result<String, ApiError> {
     let resp: MessageResponse = agent()
         .post(&format!("{api_url}/api/reset/{id}"))
-        .call()
-        .map_err(|e| e.to_string())?
-        .into_json()
-        .map_err(|e| e.to_string())?;
+        .call()?
+        .into_json()?;
     Ok(resp.message)
 }
 
+/// Perform a single-download action
+pub fn perform(api_url: &str, action: DownloadAction, id: i64) -> Result<String, ApiError> {
+    match action {
+        DownloadAction::Retry => retry_download(api_url, id),
+        DownloadAction::Delete => delete_download(api_url, id),
+        DownloadAction::Redownload => redownload(api_url, id),
+        DownloadAction::Reset => reset_download(api_url, id),
+    }
+}
+
 /// Reset all stuck downloading items
-pub fn reset_all_downloading(api_url: &str) -> Result<String, String> {
+pub fn reset_all_downloading(api_url: &str) -> Result<String, ApiError> {
     let resp: MessageResponse = agent()
         .post(&format!("{api_url}/api/reset-all-downloading"))
-        .call()
-        .map_err(|e| e.to_string())?
-        .into_json()
-        .map_err(|e| e.to_string())?;
+        .call()?
+        .into_json()?;
     Ok(resp.message)
 }
 
@@ -197,11 +283,46 @@ mod tests {
         assert!(data.system.is_none());
         assert!(data.logs.is_empty());
         assert!(data.config.is_none());
+        assert_eq!(data.failed_endpoints(), Endpoint::ALL);
     }
 
     #[test]
     fn test_fetch_counts_error() {
         let result = fetch_counts("http://127.0.0.1:19999");
-        assert!(result.is_err());
+        assert!(matches!(result, Err(ApiError::Transport(_))));
+        assert!(result.unwrap_err().is_retryable());
+    }
+
+    #[test]
+    fn test_api_error_from_status_message_body() {
+        let err = ApiError::from_status(
+            404,
+            r#"{"message":"download not found or not in error state"}"#,
+        );
+        assert_eq!(
+            err,
+            ApiError::Status {
+                code: 404,
+                message: "download not found or not in error state".into()
+            }
+        );
+        assert!(!err.is_retryable());
+        assert_eq!(err.to_string(), "Download not found or not in error state");
+    }
+
+    #[test]
+    fn test_api_error_from_status_error_body() {
+        let err = ApiError::from_status(500, r#"{"error":"Failed to load configuration"}"#);
+        assert_eq!(err.to_string(), "Failed to load configuration");
+        assert!(err.is_retryable());
+    }
+
+    #[test]
+    fn test_api_error_from_status_plain_body() {
+        let err = ApiError::from_status(502, "Bad Gateway\n");
+        assert_eq!(err.to_string(), "Bad Gateway");
+
+        let empty = ApiError::from_status(503, "");
+        assert_eq!(empty.to_string(), "Server returned HTTP 503");
     }
 }
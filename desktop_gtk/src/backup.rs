@@ -0,0 +1,191 @@
+//! Backing up a server's download queue to a file, and restoring the
+//! unfinished part of it to another server.
+//!
+//! A backup holds every download with its status, title, collection and
+//! priority. Restoring re-adds the pending and failed URLs that aren't on the
+//! target server yet; the server files them by its own collection config.
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::{ConfigResponse, Download, DownloadStatus};
+
+/// Version written to new backups; newer files are refused
+pub const FORMAT_VERSION: u32 = 1;
+
+/// A snapshot of one server's downloads
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Backup {
+    pub version: u32,
+    #[serde(rename = "createdAt")]
+    pub created_at: String,
+    /// API URL of the server the snapshot was taken from
+    pub server: String,
+    pub downloads: Vec<Download>,
+}
+
+impl Backup {
+    pub fn new(server: &str, created_at: &str, downloads: Vec<Download>) -> Self {
+        Backup {
+            version: FORMAT_VERSION,
+            created_at: created_at.to_string(),
+            server: server.to_string(),
+            downloads,
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        let mut json = serde_json::to_string_pretty(self).unwrap_or_default();
+        json.push('\n');
+        json
+    }
+
+    /// Read a backup file, refusing ones written by a newer version
+    pub fn parse(contents: &str) -> Result<Backup, String> {
+        #[derive(Deserialize)]
+        struct Versioned {
+            version: u32,
+        }
+        let versioned: Versioned =
+            serde_json::from_str(contents).map_err(|e| format!("Not a queue backup: {e}"))?;
+        if versioned.version > FORMAT_VERSION {
+            return Err(format!(
+                "Backup version {} is newer than this app supports ({FORMAT_VERSION})",
+                versioned.version
+            ));
+        }
+        serde_json::from_str(contents).map_err(|e| format!("Damaged queue backup: {e}"))
+    }
+}
+
+/// What restoring a backup to a server would do
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RestorePlan {
+    /// Pending and failed URLs to add
+    pub to_add: Vec<String>,
+    /// Already on the target server
+    pub already_present: usize,
+    /// Finished or in progress when backed up, so not re-added
+    pub finished_or_active: usize,
+    /// No collection on the target server matches, so it would skip them
+    pub unrouted: Vec<String>,
+}
+
+impl RestorePlan {
+    /// Work out the restore against the target server's downloads and config
+    pub fn new(backup: &Backup, existing: &[Download], config: &ConfigResponse) -> Self {
+        let mut plan = RestorePlan::default();
+        // URLs on the server or already planned
+        let mut seen: HashSet<&str> = existing.iter().map(|e| e.url.as_str()).collect();
+        for dl in &backup.downloads {
+            if !matches!(dl.status, DownloadStatus::Pending | DownloadStatus::Error) {
+                plan.finished_or_active += 1;
+            } else if !seen.insert(dl.url.as_str()) {
+                plan.already_present += 1;
+            } else if config.route(&dl.url).is_none() {
+                plan.unrouted.push(dl.url.clone());
+            } else {
+                plan.to_add.push(dl.url.clone());
+            }
+        }
+        plan
+    }
+
+    /// Dry-run description, e.g. "3 to add · 1 already on the server"
+    pub fn summary(&self) -> String {
+        let mut parts = vec![format!("{} to add", self.to_add.len())];
+        if self.already_present > 0 {
+            parts.push(format!("{} already on the server", self.already_present));
+        }
+        if !self.unrouted.is_empty() {
+            parts.push(format!(
+                "{} with no matching collection",
+                self.unrouted.len()
+            ));
+        }
+        if self.finished_or_active > 0 {
+            parts.push(format!(
+                "{} finished or active, skipped",
+                self.finished_or_active
+            ));
+        }
+        parts.join(" · ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::CollectionConfig;
+    use crate::types::tests::download;
+
+    fn dl(id: i64, url: &str, status: &str) -> Download {
+        Download {
+            url: url.into(),
+            collection: "yt".into(),
+            ..download(id, status)
+        }
+    }
+
+    fn config() -> ConfigResponse {
+        ConfigResponse {
+            collections: vec![(
+                "yt".into(),
+                CollectionConfig {
+                    dir: "/v".into(),
+                    command: "yt-dlp %".into(),
+                    domains: vec!["youtube.com".into()],
+                },
+            )],
+        }
+    }
+
+    #[test]
+    fn test_backup_roundtrip() {
+        let backup = Backup::new(
+            "http://nas:8001",
+            "2024-05-01T10:00:00Z",
+            vec![dl(1, "https://youtube.com/watch?v=1", "pending")],
+        );
+        let json = backup.to_json();
+        assert!(json.contains("\"version\": 1"));
+        assert!(json.contains("\"createdAt\""));
+        assert_eq!(Backup::parse(&json).unwrap(), backup);
+    }
+
+    #[test]
+    fn test_parse_rejects_other_files() {
+        assert!(Backup::parse("[]").is_err());
+        assert!(Backup::parse("not json").is_err());
+        let newer = r#"{"version": 99, "createdAt": "", "server": "", "downloads": []}"#;
+        assert!(Backup::parse(newer).unwrap_err().contains("newer"));
+    }
+
+    #[test]
+    fn test_restore_plan() {
+        let backup = Backup::new(
+            "http://nas:8001",
+            "2024-05-01T10:00:00Z",
+            vec![
+                dl(1, "https://youtube.com/watch?v=1", "pending"),
+                dl(2, "https://youtube.com/watch?v=2", "error"),
+                dl(3, "https://youtube.com/watch?v=3", "success"),
+                dl(4, "https://youtube.com/watch?v=4", "downloading"),
+                dl(5, "https://example.com/a", "pending"),
+                dl(6, "https://youtube.com/watch?v=1", "error"),
+            ],
+        );
+        let existing = vec![dl(10, "https://youtube.com/watch?v=2", "pending")];
+        let plan = RestorePlan::new(&backup, &existing, &config());
+        assert_eq!(plan.to_add, vec!["https://youtube.com/watch?v=1"]);
+        assert_eq!(plan.already_present, 2);
+        assert_eq!(plan.finished_or_active, 2);
+        assert_eq!(plan.unrouted, vec!["https://example.com/a"]);
+        assert_eq!(
+            plan.summary(),
+            "1 to add · 2 already on the server · 1 with no matching collection \
+             · 2 finished or active, skipped"
+        );
+    }
+}
@@ -0,0 +1,237 @@
+//! Command-line options for `dlm-gtk`.
+//!
+//! Options are parsed in the launching process so mistakes are reported
+//! there, then normalized back into arguments (with `--add -` replaced by the
+//! URLs read from stdin) and forwarded to the primary instance over D-Bus.
+
+use crate::scheme;
+
+/// Read URLs from stdin for `--add -`
+pub const STDIN_ARG: &str = "-";
+
+pub const USAGE: &str = "Usage: dlm-gtk [OPTIONS] [dlm://add?url=...]
+
+Options:
+  --add URL...     Queue URLs for download (\"-\" reads them from stdin)
+  --start N        Start up to N pending downloads
+  --show PAGE      Show a page: downloads, errors, logs, config
+  --api-url URL    Server to use instead of the saved setting (when no
+                   window is already open)
+  --background     Start with the window hidden
+  -h, --help       Show this help
+
+dlm:// links open a confirmation listing the URLs to add.
+
+With --add or --start and no window open, dlm-gtk runs the request without
+opening a window and exits non-zero if it fails.";
+
+/// Pages that `--show` can switch to, named as in the view stack
+pub const PAGES: &[&str] = &["downloads", "errors", "logs", "config"];
+
+/// Parsed command-line options
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CliOptions {
+    /// URLs to queue; may contain [`STDIN_ARG`] until stdin is read
+    pub add: Vec<String>,
+    /// `dlm://` links to confirm and queue
+    pub links: Vec<String>,
+    pub start: Option<i32>,
+    pub show: Option<String>,
+    pub api_url: Option<String>,
+    pub background: bool,
+    pub help: bool,
+}
+
+impl CliOptions {
+    /// Whether the URLs to add include stdin
+    pub fn reads_stdin(&self) -> bool {
+        self.add.iter().any(|u| u == STDIN_ARG)
+    }
+
+    /// Whether the options only ask for API calls, so no window is needed
+    pub fn is_headless(&self) -> bool {
+        (!self.add.is_empty() || self.start.is_some())
+            && self.links.is_empty()
+            && self.show.is_none()
+            && !self.background
+    }
+
+    /// Arguments (without the program name) that parse back to these options
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        if !self.add.is_empty() {
+            args.push("--add".to_string());
+            args.extend(self.add.iter().cloned());
+        }
+        if let Some(n) = self.start {
+            args.push(format!("--start={n}"));
+        }
+        if let Some(page) = &self.show {
+            args.push(format!("--show={page}"));
+        }
+        if let Some(url) = &self.api_url {
+            args.push(format!("--api-url={url}"));
+        }
+        if self.background {
+            args.push(crate::BACKGROUND_FLAG.to_string());
+        }
+        if self.help {
+            args.push("--help".to_string());
+        }
+        args.extend(self.links.iter().cloned());
+        args
+    }
+}
+
+/// Parse arguments, not including the program name
+pub fn parse(args: &[String]) -> Result<CliOptions, String> {
+    let mut options = CliOptions::default();
+    let mut iter = args.iter().peekable();
+
+    while let Some(arg) = iter.next() {
+        let (name, inline) = match arg.split_once('=') {
+            Some((name, value)) if name.starts_with("--") => (name, Some(value.to_string())),
+            _ => (arg.as_str(), None),
+        };
+        match name {
+            "--add" => {
+                let before = options.add.len();
+                options.add.extend(inline);
+                while let Some(url) =
+                    iter.next_if(|a| *a == STDIN_ARG || !(a.starts_with('-') || scheme::is_link(a)))
+                {
+                    options.add.push(url.clone());
+                }
+                if options.add.len() == before {
+                    return Err("--add needs at least one URL".into());
+                }
+            }
+            "--start" => {
+                let n = value(name, inline, &mut iter, "a number")?;
+                match n.parse::<i32>() {
+                    Ok(n) if n > 0 => options.start = Some(n),
+                    _ => return Err(format!("--start needs a positive number, got '{n}'")),
+                }
+            }
+            "--show" => {
+                let page = value(name, inline, &mut iter, "a page")?;
+                if !PAGES.contains(&page.as_str()) {
+                    return Err(format!(
+                        "unknown page '{page}' (expected one of: {})",
+                        PAGES.join(", ")
+                    ));
+                }
+                options.show = Some(page);
+            }
+            "--api-url" => options.api_url = Some(value(name, inline, &mut iter, "a URL")?),
+            "--background" => options.background = true,
+            "-h" | "--help" => options.help = true,
+            _ if scheme::is_link(arg) => options.links.push(arg.clone()),
+            _ if name.starts_with('-') => return Err(format!("unknown option '{name}'")),
+            _ => {
+                return Err(format!(
+                    "unexpected argument '{arg}' (use --add to queue URLs)"
+                ));
+            }
+        }
+    }
+    Ok(options)
+}
+
+/// The value of an option, given inline (`--opt=value`) or as the next argument
+fn value<'a>(
+    name: &str,
+    inline: Option<String>,
+    rest: &mut impl Iterator<Item = &'a String>,
+    what: &str,
+) -> Result<String, String> {
+    inline
+        .or_else(|| rest.next().cloned())
+        .ok_or_else(|| format!("{name} needs {what}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_empty() {
+        let options = parse(&[]).unwrap();
+        assert_eq!(options, CliOptions::default());
+        assert!(!options.is_headless());
+    }
+
+    #[test]
+    fn test_parse_add_and_start() {
+        let options = parse(&args(&[
+            "--add",
+            "https://a.example/1",
+            "https://b.example/2",
+            "--start",
+            "3",
+        ]))
+        .unwrap();
+        assert_eq!(
+            options.add,
+            args(&["https://a.example/1", "https://b.example/2"])
+        );
+        assert_eq!(options.start, Some(3));
+        assert!(options.is_headless());
+        assert!(!options.reads_stdin());
+    }
+
+    #[test]
+    fn test_parse_stdin_and_inline_values() {
+        let options = parse(&args(&[
+            "--add",
+            "-",
+            "--show=errors",
+            "--api-url=http://nas:8001",
+        ]))
+        .unwrap();
+        assert!(options.reads_stdin());
+        assert_eq!(options.show.as_deref(), Some("errors"));
+        assert_eq!(options.api_url.as_deref(), Some("http://nas:8001"));
+        // Showing a page needs the window
+        assert!(!options.is_headless());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse(&args(&["--add"])).is_err());
+        assert!(parse(&args(&["--add", "--start", "1"])).is_err());
+        assert!(parse(&args(&["--start", "zero"])).is_err());
+        assert!(parse(&args(&["--start", "0"])).is_err());
+        assert!(parse(&args(&["--start"])).is_err());
+        assert!(parse(&args(&["--show", "stats"])).is_err());
+        assert!(parse(&args(&["--frobnicate"])).is_err());
+        assert!(parse(&args(&["https://a.example/1"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_links() {
+        let options = parse(&args(&["dlm://add?url=https%3A%2F%2Fa.example%2F1"])).unwrap();
+        assert_eq!(options.links.len(), 1);
+        // Links need the confirmation sheet
+        assert!(!options.is_headless());
+    }
+
+    #[test]
+    fn test_to_args_roundtrip() {
+        let options = CliOptions {
+            add: args(&["https://a.example/1"]),
+            start: Some(2),
+            show: Some("logs".into()),
+            api_url: Some("http://localhost:9000".into()),
+            links: args(&["dlm://add?url=https%3A%2F%2Fb.example%2F2"]),
+            background: true,
+            help: false,
+        };
+        assert_eq!(parse(&options.to_args()).unwrap(), options);
+        assert!(CliOptions::default().to_args().is_empty());
+    }
+}
@@ -0,0 +1,109 @@
+//! Offering to queue links copied in other apps.
+//!
+//! When enabled in Settings, each new clipboard text that is a single URL
+//! routed to a configured collection, and not already downloaded, is offered
+//! with a one-click "Add": as a toast while the window is shown, or as a
+//! notification while running in the background.
+//!
+//! Wayland only tells focused apps about clipboard changes, so there the
+//! clipboard is also checked whenever the window regains focus.
+
+use crate::types::{AppState, is_web_url};
+
+/// Notification id for the offer, so a newer copy replaces the old one
+pub const NOTIFICATION_ID: &str = "clipboard-offer";
+/// Longest URL shown in full in the offer toast
+const MAX_TOAST_URL_CHARS: usize = 60;
+
+/// The URL in copied text worth offering, with the collection it would go to
+pub fn offer_for<'a>(state: &'a AppState, text: &str) -> Option<(String, &'a str)> {
+    let url = text.trim();
+    if url.contains(char::is_whitespace) || !is_web_url(url) {
+        return None;
+    }
+    if state.clipboard_offered.as_deref() == Some(url) || state.download_for_url(url).is_some() {
+        return None;
+    }
+    let collection = state.route_url(url)?;
+    Some((url.to_string(), collection))
+}
+
+/// Title for the offer toast, shortening long URLs
+pub fn toast_title(url: &str, collection: &str) -> String {
+    let shown = if url.chars().count() > MAX_TOAST_URL_CHARS {
+        let cut: String = url.chars().take(MAX_TOAST_URL_CHARS - 1).collect();
+        format!("{cut}…")
+    } else {
+        url.to_string()
+    };
+    format!("Queue in {collection}? {shown}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{CollectionConfig, ConfigResponse, Download};
+
+    fn state() -> AppState {
+        AppState {
+            config: Some(ConfigResponse {
+                collections: vec![(
+                    "yt".into(),
+                    CollectionConfig {
+                        dir: "/v".into(),
+                        command: "yt-dlp %".into(),
+                        domains: vec!["youtube.com".into()],
+                    },
+                )],
+            }),
+            downloads: vec![Download {
+                id: 1,
+                url: "https://youtube.com/watch?v=old".into(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_offer_for_routed_url() {
+        let state = state();
+        assert_eq!(
+            offer_for(&state, "  https://youtube.com/watch?v=new\n"),
+            Some(("https://youtube.com/watch?v=new".to_string(), "yt"))
+        );
+    }
+
+    #[test]
+    fn test_toast_title() {
+        assert_eq!(
+            toast_title("https://youtu.be/x", "yt"),
+            "Queue in yt? https://youtu.be/x"
+        );
+        let long = format!("https://youtube.com/watch?v={}", "a".repeat(80));
+        let title = toast_title(&long, "yt");
+        assert!(title.ends_with('…'));
+        assert_eq!(
+            title.chars().count(),
+            "Queue in yt? ".len() + MAX_TOAST_URL_CHARS
+        );
+    }
+
+    #[test]
+    fn test_offer_for_skips_uninteresting_text() {
+        let mut state = state();
+        // Not a lone URL
+        assert_eq!(
+            offer_for(&state, "watch https://youtube.com/watch?v=new"),
+            None
+        );
+        assert_eq!(offer_for(&state, "hello"), None);
+        // No collection for it
+        assert_eq!(offer_for(&state, "https://example.com/a"), None);
+        // Already downloaded
+        assert_eq!(offer_for(&state, "https://youtube.com/watch?v=old"), None);
+        // Already offered
+        state.clipboard_offered = Some("https://youtube.com/watch?v=new".into());
+        assert_eq!(offer_for(&state, "https://youtube.com/watch?v=new"), None);
+    }
+}
@@ -2,25 +2,60 @@ use std::path::PathBuf;
 
 use serde::{Deserialize, Serialize};
 
-use crate::CONFIG_DIR;
+use crate::types::{GroupBy, SavedView, SortOrder};
+use crate::{BACKGROUND_FLAG, CONFIG_DIR};
 
 const DEFAULT_API_URL: &str = "http://localhost:8001";
+const AUTOSTART_DIR: &str = ".config/autostart";
+const AUTOSTART_FILE: &str = "dlm-gtk.desktop";
 
 /// Application settings
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct AppSettings {
     /// URL of the DLM API server
     pub api_url: String,
+    /// Notify when a download finishes
+    #[serde(default = "default_true")]
+    pub notify_success: bool,
+    /// Notify when a download fails
+    #[serde(default = "default_true")]
+    pub notify_errors: bool,
+    /// Hide the window on close and keep refreshing in the background
+    #[serde(default)]
+    pub run_in_background: bool,
+    /// Offer to queue links copied in other apps
+    #[serde(default)]
+    pub watch_clipboard: bool,
+    /// Named queries listed in the sidebar
+    #[serde(default)]
+    pub saved_views: Vec<SavedView>,
+    /// Sort order of the Downloads page
+    #[serde(default)]
+    pub sort_order: SortOrder,
+    /// Grouping of the Downloads page
+    #[serde(default)]
+    pub group_by: GroupBy,
 }
 
 impl Default for AppSettings {
     fn default() -> Self {
         Self {
             api_url: DEFAULT_API_URL.to_string(),
+            notify_success: true,
+            notify_errors: true,
+            run_in_background: false,
+            watch_clipboard: false,
+            saved_views: Vec::new(),
+            sort_order: SortOrder::default(),
+            group_by: GroupBy::None,
         }
     }
 }
 
+fn default_true() -> bool {
+    true
+}
+
 /// Get the config directory path
 pub fn get_config_dir() -> PathBuf {
     let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
@@ -50,6 +85,53 @@ pub fn save_settings(settings: &AppSettings) {
     }
 }
 
+/// Path of the XDG autostart entry
+pub fn autostart_path() -> PathBuf {
+    let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+    PathBuf::from(home).join(AUTOSTART_DIR).join(AUTOSTART_FILE)
+}
+
+/// Whether the app is set to start on login
+pub fn autostart_enabled() -> bool {
+    autostart_path().exists()
+}
+
+/// Contents of the autostart entry, starting `exec` hidden in the background
+fn autostart_entry(exec: &str) -> String {
+    let exec = if exec.contains(char::is_whitespace) {
+        format!("\"{exec}\"")
+    } else {
+        exec.to_string()
+    };
+    format!(
+        "[Desktop Entry]
+Name=DLM
+Comment=Download Manager desktop client
+Exec={exec} {BACKGROUND_FLAG}
+Icon=folder-download-symbolic
+Terminal=false
+Type=Application
+X-GNOME-Autostart-enabled=true
+"
+    )
+}
+
+/// Write or remove the autostart entry for the running executable
+pub fn set_autostart(enabled: bool) -> std::io::Result<()> {
+    let path = autostart_path();
+    if !enabled {
+        return match std::fs::remove_file(&path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        };
+    }
+    let exe = std::env::current_exe()?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(&path, autostart_entry(&exe.to_string_lossy()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -70,6 +152,17 @@ mod tests {
     fn test_settings_serialization_roundtrip() {
         let settings = AppSettings {
             api_url: "http://example.com:9000".into(),
+            notify_success: false,
+            notify_errors: true,
+            run_in_background: true,
+            watch_clipboard: true,
+            saved_views: vec![SavedView {
+                name: "Reddit errors".into(),
+                query: "domain:reddit.com status:error".into(),
+                sort: SortOrder::new(crate::types::SortKey::Collection),
+            }],
+            sort_order: SortOrder::new(crate::types::SortKey::Title),
+            group_by: GroupBy::Domain,
         };
 
         let json = serde_json::to_string(&settings).unwrap();
@@ -82,10 +175,39 @@ mod tests {
     fn test_settings_json_format() {
         let settings = AppSettings {
             api_url: "http://localhost:8001".into(),
+            ..Default::default()
         };
 
         let json = serde_json::to_string_pretty(&settings).unwrap();
         assert!(json.contains("api_url"));
         assert!(json.contains("http://localhost:8001"));
     }
+
+    #[test]
+    fn test_settings_missing_fields_use_defaults() {
+        let parsed: AppSettings =
+            serde_json::from_str(r#"{"api_url":"http://example.com:9000"}"#).unwrap();
+        assert_eq!(parsed.api_url, "http://example.com:9000");
+        assert!(parsed.notify_success);
+        assert!(parsed.notify_errors);
+        assert!(!parsed.run_in_background);
+        assert!(!parsed.watch_clipboard);
+        assert!(parsed.saved_views.is_empty());
+    }
+
+    #[test]
+    fn test_autostart_entry() {
+        let entry = autostart_entry("/home/user/.local/bin/dlm-gtk");
+        assert!(entry.starts_with("[Desktop Entry]\n"));
+        assert!(entry.contains("\nExec=/home/user/.local/bin/dlm-gtk --background\n"));
+        assert!(entry.contains("\nType=Application\n"));
+
+        let spaced = autostart_entry("/opt/My Apps/dlm-gtk");
+        assert!(spaced.contains("\nExec=\"/opt/My Apps/dlm-gtk\" --background\n"));
+    }
+
+    #[test]
+    fn test_autostart_path() {
+        assert!(autostart_path().ends_with(".config/autostart/dlm-gtk.desktop"));
+    }
 }
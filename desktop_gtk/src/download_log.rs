@@ -0,0 +1,150 @@
+//! Reader for the per-collection `downloads.log` written by the server.
+//!
+//! Every download run appends a block of the form:
+//!
+//! ```text
+//! === Download 42 - 2026-10-01T12:00:00.000Z ===
+//! URL: https://...
+//! Command: yt-dlp https://...
+//! --- STDOUT ---
+//! ...
+//! --- STDERR ---
+//! ...
+//! --- END ---
+//! ```
+
+use std::io;
+use std::path::Path;
+
+const LOG_FILE: &str = "downloads.log";
+const HEADER_PREFIX: &str = "=== Download ";
+const HEADER_SUFFIX: &str = " ===";
+const END_MARKER: &str = "--- END ---";
+
+/// One recorded run of a download's command
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogBlock {
+    /// When the run finished, as written in the header
+    pub timestamp: String,
+    /// The whole block, header through end marker
+    pub text: String,
+}
+
+/// Read the blocks for download `id` from the `downloads.log` in `dir`,
+/// oldest first
+pub fn read_blocks(dir: &str, id: i64) -> io::Result<Vec<LogBlock>> {
+    let contents = std::fs::read_to_string(Path::new(dir).join(LOG_FILE))?;
+    Ok(blocks_for(&contents, id))
+}
+
+/// Extract the blocks for download `id` from the contents of a log file
+pub fn blocks_for(contents: &str, id: i64) -> Vec<LogBlock> {
+    let mut blocks = Vec::new();
+    // Whether we're inside any download's block, matching or not
+    let mut in_block = false;
+    let mut current: Option<LogBlock> = None;
+
+    for line in contents.lines() {
+        if !in_block {
+            if let Some((block_id, timestamp)) = parse_header(line) {
+                in_block = true;
+                if block_id == id {
+                    current = Some(LogBlock {
+                        timestamp: timestamp.to_string(),
+                        text: line.to_string(),
+                    });
+                }
+            }
+            continue;
+        }
+        if let Some(block) = current.as_mut() {
+            block.text.push('\n');
+            block.text.push_str(line);
+        }
+        if line == END_MARKER {
+            in_block = false;
+            blocks.extend(current.take());
+        }
+    }
+    // A run still being written has no end marker yet
+    blocks.extend(current);
+    blocks
+}
+
+/// Parse `=== Download {id} - {timestamp} ===` into its id and timestamp
+fn parse_header(line: &str) -> Option<(i64, &str)> {
+    let inner = line
+        .strip_prefix(HEADER_PREFIX)?
+        .strip_suffix(HEADER_SUFFIX)?;
+    let (id, timestamp) = inner.split_once(" - ")?;
+    Some((id.parse().ok()?, timestamp))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOG: &str = "
+=== Download 7 - 2026-10-01T12:00:00.000Z ===
+URL: https://example.com/a
+Command: yt-dlp https://example.com/a
+--- STDOUT ---
+=== Download 8 - not a real header ===
+--- STDERR ---
+ERROR: Unsupported URL
+--- END ---
+
+
+=== Download 8 - 2026-10-01T12:05:00.000Z ===
+URL: https://example.com/b
+Command: yt-dlp https://example.com/b
+--- STDOUT ---
+done
+--- STDERR ---
+
+--- END ---
+
+
+=== Download 7 - 2026-10-02T08:00:00.000Z ===
+URL: https://example.com/a
+Command: yt-dlp https://example.com/a
+--- STDOUT ---
+";
+
+    #[test]
+    fn test_parse_header() {
+        assert_eq!(
+            parse_header("=== Download 12 - 2026-10-01T12:00:00.000Z ==="),
+            Some((12, "2026-10-01T12:00:00.000Z"))
+        );
+        assert_eq!(parse_header("=== Download x - y ==="), None);
+        assert_eq!(parse_header("URL: https://example.com"), None);
+    }
+
+    #[test]
+    fn test_blocks_for_matches_id_only() {
+        let blocks = blocks_for(LOG, 8);
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].timestamp, "2026-10-01T12:05:00.000Z");
+        assert!(blocks[0].text.ends_with("--- END ---"));
+        assert!(blocks[0].text.contains("done"));
+    }
+
+    #[test]
+    fn test_blocks_for_keeps_runs_and_unfinished_block() {
+        let blocks = blocks_for(LOG, 7);
+        assert_eq!(blocks.len(), 2);
+        // Header-like output inside a block doesn't start a new one
+        assert!(blocks[0].text.contains("not a real header"));
+        assert!(blocks[0].text.contains("ERROR: Unsupported URL"));
+        assert_eq!(blocks[1].timestamp, "2026-10-02T08:00:00.000Z");
+        assert!(!blocks[1].text.contains(END_MARKER));
+
+        assert!(blocks_for(LOG, 99).is_empty());
+    }
+
+    #[test]
+    fn test_read_blocks_missing_file() {
+        assert!(read_blocks("/nonexistent/dlm-collection", 1).is_err());
+    }
+}
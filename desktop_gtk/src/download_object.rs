@@ -0,0 +1,354 @@
+//! GObject wrappers around [`Download`]s and group headers so they can live
+//! in a `gio::ListStore` and be rendered by `gtk::ListView`.
+
+use std::cell::{Ref, RefCell};
+use std::collections::{HashMap, HashSet};
+
+use gtk::gio;
+use gtk::glib;
+use gtk::prelude::*;
+use gtk::subclass::prelude::*;
+
+use crate::types::{Download, GroupHeader};
+
+mod imp {
+    use super::*;
+
+    #[derive(Default)]
+    pub struct DownloadObject {
+        pub download: RefCell<Download>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for DownloadObject {
+        const NAME: &'static str = "DlmDownloadObject";
+        type Type = super::DownloadObject;
+    }
+
+    impl ObjectImpl for DownloadObject {}
+
+    #[derive(Default)]
+    pub struct GroupObject {
+        pub header: RefCell<GroupHeader>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for GroupObject {
+        const NAME: &'static str = "DlmGroupObject";
+        type Type = super::GroupObject;
+    }
+
+    impl ObjectImpl for GroupObject {}
+}
+
+glib::wrapper! {
+    pub struct DownloadObject(ObjectSubclass<imp::DownloadObject>);
+}
+
+impl DownloadObject {
+    pub fn new(download: Download) -> Self {
+        let obj: Self = glib::Object::new();
+        obj.imp().download.replace(download);
+        obj
+    }
+
+    pub fn download(&self) -> Ref<'_, Download> {
+        self.imp().download.borrow()
+    }
+
+    pub fn id(&self) -> i64 {
+        self.download().id
+    }
+
+    fn set_download(&self, download: Download) {
+        self.imp().download.replace(download);
+    }
+}
+
+glib::wrapper! {
+    /// The header above a group of downloads
+    pub struct GroupObject(ObjectSubclass<imp::GroupObject>);
+}
+
+impl GroupObject {
+    pub fn new(header: GroupHeader) -> Self {
+        let obj: Self = glib::Object::new();
+        obj.imp().header.replace(header);
+        obj
+    }
+
+    pub fn header(&self) -> Ref<'_, GroupHeader> {
+        self.imp().header.borrow()
+    }
+
+    fn set_header(&self, header: GroupHeader) {
+        self.imp().header.replace(header);
+    }
+}
+
+/// An item of a downloads list
+#[derive(Debug, Clone)]
+pub enum ListEntry<'a> {
+    Group(GroupHeader),
+    Download(&'a Download),
+}
+
+/// What list items are matched by between updates
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum EntryKey {
+    Group(String),
+    Download(i64),
+}
+
+impl ListEntry<'_> {
+    fn key(&self) -> EntryKey {
+        match self {
+            ListEntry::Group(header) => EntryKey::Group(header.key.clone()),
+            ListEntry::Download(dl) => EntryKey::Download(dl.id),
+        }
+    }
+
+    fn to_object(&self) -> glib::Object {
+        match self {
+            ListEntry::Group(header) => GroupObject::new(header.clone()).upcast(),
+            ListEntry::Download(dl) => DownloadObject::new((*dl).clone()).upcast(),
+        }
+    }
+
+    /// Whether `obj` already shows this entry as it is
+    fn is_shown_by(&self, obj: &glib::Object) -> bool {
+        match self {
+            ListEntry::Group(header) => obj
+                .downcast_ref::<GroupObject>()
+                .is_some_and(|o| *o.header() == *header),
+            ListEntry::Download(dl) => obj
+                .downcast_ref::<DownloadObject>()
+                .is_some_and(|o| *o.download() == **dl),
+        }
+    }
+
+    /// Update `obj`, an object for the same key, to show this entry
+    fn update(&self, obj: &glib::Object) {
+        match self {
+            ListEntry::Group(header) => {
+                if let Some(o) = obj.downcast_ref::<GroupObject>() {
+                    o.set_header(header.clone());
+                }
+            }
+            ListEntry::Download(dl) => {
+                if let Some(o) = obj.downcast_ref::<DownloadObject>() {
+                    o.set_download((*dl).clone());
+                }
+            }
+        }
+    }
+}
+
+fn object_key(obj: &glib::Object) -> Option<EntryKey> {
+    if let Some(o) = obj.downcast_ref::<DownloadObject>() {
+        Some(EntryKey::Download(o.id()))
+    } else {
+        obj.downcast_ref::<GroupObject>()
+            .map(|o| EntryKey::Group(o.header().key.clone()))
+    }
+}
+
+/// Bring `store` in line with `downloads`, matching items by id.
+///
+/// Unchanged downloads keep their object, so their rows are not rebound, and
+/// removals and insertions only touch the affected positions. A reorder (e.g.
+/// after changing the sort) replaces the whole list in a single splice.
+pub fn sync_store(store: &gio::ListStore, downloads: &[&Download]) {
+    let entries: Vec<ListEntry> = downloads.iter().map(|d| ListEntry::Download(d)).collect();
+    sync_entries(store, &entries);
+}
+
+/// [`sync_store`] for a list that may include group headers, matched by key
+pub fn sync_entries(store: &gio::ListStore, entries: &[ListEntry]) {
+    let mut current: Vec<(EntryKey, glib::Object)> = (0..store.n_items())
+        .filter_map(|i| store.item(i))
+        .filter_map(|obj| Some((object_key(&obj)?, obj)))
+        .collect();
+
+    // Drop items that are gone, back to front so positions stay valid
+    let wanted: HashSet<EntryKey> = entries.iter().map(ListEntry::key).collect();
+    for i in (0..current.len()).rev() {
+        if !wanted.contains(&current[i].0) {
+            store.remove(i as u32);
+            current.remove(i);
+        }
+    }
+
+    // If the surviving items aren't already in target order, rebuild
+    let present: HashSet<&EntryKey> = current.iter().map(|(key, _)| key).collect();
+    let in_order = current.iter().map(|(key, _)| key.clone()).eq(entries
+        .iter()
+        .map(ListEntry::key)
+        .filter(|key| present.contains(key)));
+    if !in_order {
+        let by_key: HashMap<EntryKey, glib::Object> = current.into_iter().collect();
+        let objects: Vec<glib::Object> = entries
+            .iter()
+            .map(|entry| match by_key.get(&entry.key()) {
+                Some(obj) => {
+                    entry.update(obj);
+                    obj.clone()
+                }
+                None => entry.to_object(),
+            })
+            .collect();
+        store.splice(0, store.n_items(), &objects);
+        return;
+    }
+
+    // Insert new items and replace changed ones in place
+    for (i, entry) in entries.iter().enumerate() {
+        match current.get(i) {
+            Some((key, obj)) if *key == entry.key() => {
+                if !entry.is_shown_by(obj) {
+                    let obj = entry.to_object();
+                    store.splice(i as u32, 1, std::slice::from_ref(&obj));
+                    current[i].1 = obj;
+                }
+            }
+            _ => {
+                let obj = entry.to_object();
+                store.insert(i as u32, &obj);
+                current.insert(i, (entry.key(), obj));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::DownloadStatus;
+    use crate::types::tests::download;
+
+    fn ids(store: &gio::ListStore) -> Vec<i64> {
+        (0..store.n_items())
+            .filter_map(|i| store.item(i).and_downcast::<DownloadObject>())
+            .map(|o| o.id())
+            .collect()
+    }
+
+    fn object_at(store: &gio::ListStore, i: u32) -> glib::Object {
+        store.item(i).unwrap()
+    }
+
+    #[test]
+    fn test_sync_store_fills_empty_store() {
+        let store = gio::ListStore::new::<DownloadObject>();
+        let (a, b) = (download(2, "pending"), download(1, "success"));
+        sync_store(&store, &[&a, &b]);
+        assert_eq!(ids(&store), vec![2, 1]);
+    }
+
+    #[test]
+    fn test_sync_store_keeps_unchanged_objects() {
+        let store = gio::ListStore::new::<DownloadObject>();
+        let (a, b, c) = (
+            download(3, "pending"),
+            download(2, "pending"),
+            download(1, "success"),
+        );
+        sync_store(&store, &[&a, &b, &c]);
+        let first = object_at(&store, 0);
+        let last = object_at(&store, 2);
+
+        let b2 = download(2, "downloading");
+        sync_store(&store, &[&a, &b2, &c]);
+
+        assert_eq!(ids(&store), vec![3, 2, 1]);
+        assert_eq!(object_at(&store, 0), first);
+        assert_eq!(object_at(&store, 2), last);
+        let middle = object_at(&store, 1).downcast::<DownloadObject>().unwrap();
+        assert_eq!(middle.download().status, DownloadStatus::Downloading);
+    }
+
+    #[test]
+    fn test_sync_store_inserts_and_removes() {
+        let store = gio::ListStore::new::<DownloadObject>();
+        let (a, b, c) = (
+            download(3, "pending"),
+            download(2, "pending"),
+            download(1, "pending"),
+        );
+        sync_store(&store, &[&b, &c]);
+        let kept = object_at(&store, 0);
+
+        sync_store(&store, &[&a, &b]);
+        assert_eq!(ids(&store), vec![3, 2]);
+        assert_eq!(object_at(&store, 1), kept);
+    }
+
+    #[test]
+    fn test_sync_entries_with_groups() {
+        let store = gio::ListStore::new::<glib::Object>();
+        let (a, b) = (download(2, "pending"), download(1, "error"));
+        let header = |key: &str, count: usize, collapsed: bool| GroupHeader {
+            key: key.into(),
+            title: key.into(),
+            count,
+            collapsed,
+            action_counts: Vec::new(),
+        };
+        let keys = |store: &gio::ListStore| -> Vec<String> {
+            (0..store.n_items())
+                .filter_map(|i| store.item(i))
+                .map(|obj| match obj.downcast::<GroupObject>() {
+                    Ok(group) => group.header().key.clone(),
+                    Err(obj) => obj.downcast::<DownloadObject>().unwrap().id().to_string(),
+                })
+                .collect()
+        };
+        sync_entries(
+            &store,
+            &[
+                ListEntry::Group(header("pending", 1, false)),
+                ListEntry::Download(&a),
+                ListEntry::Group(header("error", 1, false)),
+                ListEntry::Download(&b),
+            ],
+        );
+        assert_eq!(keys(&store), vec!["pending", "2", "error", "1"]);
+        let pending = object_at(&store, 0);
+
+        // Collapsing replaces the header and drops its downloads
+        sync_entries(
+            &store,
+            &[
+                ListEntry::Group(header("pending", 1, false)),
+                ListEntry::Download(&a),
+                ListEntry::Group(header("error", 1, true)),
+            ],
+        );
+        assert_eq!(keys(&store), vec!["pending", "2", "error"]);
+        assert_eq!(object_at(&store, 0), pending);
+        let error = object_at(&store, 2).downcast::<GroupObject>().unwrap();
+        assert!(error.header().collapsed);
+
+        sync_store(&store, &[&b, &a]);
+        assert_eq!(keys(&store), vec!["1", "2"]);
+    }
+
+    #[test]
+    fn test_sync_store_reorders() {
+        let store = gio::ListStore::new::<DownloadObject>();
+        let (a, b, c) = (
+            download(1, "pending"),
+            download(2, "pending"),
+            download(3, "pending"),
+        );
+        sync_store(&store, &[&a, &b, &c]);
+        let obj_b = object_at(&store, 1);
+
+        sync_store(&store, &[&c, &b, &a]);
+        assert_eq!(ids(&store), vec![3, 2, 1]);
+        assert_eq!(object_at(&store, 1), obj_b);
+
+        sync_store(&store, &[]);
+        assert_eq!(store.n_items(), 0);
+    }
+}
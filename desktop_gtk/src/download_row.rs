@@ -0,0 +1,230 @@
+//! Row widget for a download in the Downloads and Errors lists.
+//!
+//! Rows are created once by a list factory and rebound to different
+//! downloads as the list scrolls or changes. Buttons trigger the `app.*`
+//! download actions with the download id as target, so binding never
+//! connects signal handlers.
+
+use std::cell::Cell;
+
+use gtk::glib;
+use gtk::prelude::*;
+use gtk::subclass::prelude::*;
+
+use crate::search;
+use crate::time::{self, Timestamp};
+use crate::types::{Download, DownloadAction, DownloadStatus};
+
+/// What rows are bound with besides their download
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RowContext {
+    /// Search terms to highlight
+    pub highlight: Vec<String>,
+    /// The time relative times are measured from, to the minute
+    pub now: Timestamp,
+}
+
+mod imp {
+    use super::*;
+
+    #[derive(Default)]
+    pub struct DownloadRow {
+        pub title_label: gtk::Label,
+        pub url_label: gtk::Label,
+        pub meta_label: gtk::Label,
+        pub time_label: gtk::Label,
+        pub error_label: gtk::Label,
+        pub status_label: gtk::Label,
+        pub open_button: gtk::Button,
+        pub action_button: gtk::Button,
+        pub delete_button: gtk::Button,
+        /// Errors page layout: error message and delete button, no status badge
+        pub errors_style: Cell<bool>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for DownloadRow {
+        const NAME: &'static str = "DlmDownloadRow";
+        type Type = super::DownloadRow;
+        type ParentType = gtk::Box;
+    }
+
+    impl ObjectImpl for DownloadRow {
+        fn constructed(&self) {
+            self.parent_constructed();
+            let obj = self.obj();
+            obj.set_orientation(gtk::Orientation::Horizontal);
+            obj.set_spacing(8);
+            obj.set_margin_top(6);
+            obj.set_margin_bottom(6);
+            obj.set_margin_start(12);
+            obj.set_margin_end(12);
+
+            // Info column
+            let info_box = gtk::Box::builder()
+                .orientation(gtk::Orientation::Vertical)
+                .spacing(2)
+                .hexpand(true)
+                .build();
+
+            self.title_label.set_halign(gtk::Align::Start);
+            self.title_label
+                .set_ellipsize(gtk::pango::EllipsizeMode::End);
+            self.title_label.set_css_classes(&["heading"]);
+
+            self.url_label.set_halign(gtk::Align::Start);
+            self.url_label.set_ellipsize(gtk::pango::EllipsizeMode::End);
+            self.url_label.set_css_classes(&["dim-label", "caption"]);
+
+            self.meta_label.set_css_classes(&["dim-label", "caption"]);
+            self.time_label.set_css_classes(&["dim-label", "caption"]);
+            let meta_box = gtk::Box::builder()
+                .orientation(gtk::Orientation::Horizontal)
+                .spacing(6)
+                .halign(gtk::Align::Start)
+                .build();
+            meta_box.append(&self.meta_label);
+            meta_box.append(&self.time_label);
+
+            self.error_label.set_halign(gtk::Align::Start);
+            self.error_label.set_wrap(true);
+            self.error_label.set_xalign(0.0);
+            self.error_label.set_css_classes(&["error", "caption"]);
+
+            info_box.append(&self.title_label);
+            info_box.append(&self.url_label);
+            info_box.append(&meta_box);
+            info_box.append(&self.error_label);
+
+            self.status_label.set_valign(gtk::Align::Center);
+
+            // Action buttons
+            let actions_box = gtk::Box::builder()
+                .orientation(gtk::Orientation::Horizontal)
+                .spacing(2)
+                .valign(gtk::Align::Center)
+                .build();
+
+            self.open_button.set_icon_name("folder-open-symbolic");
+            self.open_button
+                .set_tooltip_text(Some("Open download folder"));
+            self.open_button.set_css_classes(&["flat", "circular"]);
+
+            self.action_button.set_css_classes(&["flat", "circular"]);
+
+            self.delete_button
+                .set_icon_name(DownloadAction::Delete.icon_name());
+            self.delete_button
+                .set_tooltip_text(Some(DownloadAction::Delete.label()));
+            self.delete_button.set_css_classes(&["flat", "circular"]);
+
+            actions_box.append(&self.open_button);
+            actions_box.append(&self.action_button);
+            actions_box.append(&self.delete_button);
+
+            obj.append(&info_box);
+            obj.append(&self.status_label);
+            obj.append(&actions_box);
+        }
+    }
+
+    impl WidgetImpl for DownloadRow {}
+    impl BoxImpl for DownloadRow {}
+}
+
+glib::wrapper! {
+    pub struct DownloadRow(ObjectSubclass<imp::DownloadRow>)
+        @extends gtk::Box, gtk::Widget,
+        @implements gtk::Accessible, gtk::Buildable, gtk::ConstraintTarget, gtk::Orientable;
+}
+
+impl DownloadRow {
+    pub fn new(errors_style: bool) -> Self {
+        let row: Self = glib::Object::new();
+        row.imp().errors_style.set(errors_style);
+        row
+    }
+
+    /// Show a download in this row
+    pub fn bind(&self, dl: &Download, context: &RowContext) {
+        let imp = self.imp();
+        let errors_style = imp.errors_style.get();
+        let highlight = &context.highlight;
+
+        imp.title_label
+            .set_markup(&search::highlight(dl.display_title(), highlight));
+        imp.url_label
+            .set_markup(&search::highlight(&dl.url, highlight));
+        let (text, tooltip) = time_text(dl, context.now);
+        let separator = if text.is_empty() { "" } else { " ·" };
+        imp.meta_label.set_markup(&search::highlight(
+            &format!("{} · ID {}{separator}", dl.collection, dl.id),
+            highlight,
+        ));
+        imp.time_label.set_text(&text);
+        imp.time_label
+            .set_tooltip_text(Some(tooltip.as_str()).filter(|t| !t.is_empty()));
+        imp.time_label.set_visible(!text.is_empty());
+
+        let error = dl.error_message.as_deref().filter(|_| errors_style);
+        imp.error_label
+            .set_markup(&search::highlight(error.unwrap_or_default(), highlight));
+        imp.error_label.set_visible(error.is_some());
+
+        imp.status_label.set_label(dl.status.name());
+        imp.status_label
+            .set_css_classes(&[dl.status.css_class(), "status-badge"]);
+        imp.status_label.set_visible(!errors_style);
+
+        imp.open_button
+            .set_action_target_value(Some(&dl.collection.to_variant()));
+        imp.open_button.set_action_name(Some("app.open-folder"));
+
+        match DownloadAction::for_status(&dl.status) {
+            Some(action) => {
+                imp.action_button.set_icon_name(action.icon_name());
+                imp.action_button.set_tooltip_text(Some(action.label()));
+                bind_action(&imp.action_button, action, dl.id);
+                imp.action_button.set_visible(true);
+            }
+            None => imp.action_button.set_visible(false),
+        }
+
+        bind_action(&imp.delete_button, DownloadAction::Delete, dl.id);
+        imp.delete_button.set_visible(errors_style);
+    }
+}
+
+/// When a download was queued, or finished and how long that took, with
+/// the absolute times for a tooltip
+fn time_text(dl: &Download, now: Timestamp) -> (String, String) {
+    let queued = dl.created_at.map(|t| format!("Queued {}", t.local()));
+    let finished = dl
+        .downloaded_at
+        .filter(|_| dl.status == DownloadStatus::Success);
+    match (finished, dl.created_at) {
+        (Some(done), _) => {
+            let mut text = format!("finished {}", time::relative(done, now));
+            if let Some(waited) = dl.time_in_queue() {
+                text.push_str(&format!(" · {} in queue", time::duration(waited)));
+            }
+            let tooltip: Vec<String> = queued
+                .into_iter()
+                .chain([format!("Finished {}", done.local())])
+                .collect();
+            (text, tooltip.join("\n"))
+        }
+        (None, Some(created)) => (
+            format!("queued {}", time::relative(created, now)),
+            queued.unwrap_or_default(),
+        ),
+        // Unreadable time from the server
+        (None, None) => (String::new(), String::new()),
+    }
+}
+
+/// Point a button at the `app.*` action for a download
+fn bind_action(button: &gtk::Button, action: DownloadAction, id: i64) {
+    button.set_action_target_value(Some(&id.to_variant()));
+    button.set_action_name(Some(&format!("app.{}", action.name())));
+}
@@ -0,0 +1,265 @@
+//! Live updates from the server over Server-Sent Events.
+//!
+//! The client subscribes to `GET /api/events` and expects `text/event-stream`
+//! messages of the form:
+//!
+//! ```text
+//! event: download
+//! data: {"id":1,"collection":"yt","status":"success",...}
+//!
+//! event: delete
+//! data: {"id":1}
+//!
+//! event: log
+//! data: {"line":"[ERROR] ..."}
+//! ```
+//!
+//! Comment lines (`: keepalive`) and unknown event types are ignored. Servers
+//! that don't offer the stream answer 404, and the client keeps polling.
+
+use std::io::{BufRead, BufReader};
+use std::time::Duration;
+
+use serde::Deserialize;
+
+use crate::api::{ApiError, USER_AGENT};
+use crate::types::Download;
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+/// Idle streams are dropped and reconnected after this long without a byte;
+/// servers should send a comment line more often than this as a keepalive
+const READ_TIMEOUT: Duration = Duration::from_secs(90);
+
+/// An update pushed by the server
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ServerEvent {
+    /// A download was added or changed
+    Download(Download),
+    /// A download was removed
+    Deleted(i64),
+    /// A line was appended to the server log
+    Log(String),
+}
+
+#[derive(Deserialize)]
+struct DeletePayload {
+    id: i64,
+}
+
+#[derive(Deserialize)]
+struct LogPayload {
+    line: String,
+}
+
+/// Whether a subscription error means the server has no event stream at all,
+/// as opposed to a temporary failure worth reconnecting after
+pub fn is_unsupported(err: &ApiError) -> bool {
+    matches!(err, ApiError::Status { code, .. } if matches!(code, 404 | 405 | 501))
+}
+
+/// Subscribe to the event stream, blocking until it ends. `on_open` is called
+/// once the server accepts the subscription, then `on_event` for each event;
+/// it returns whether to keep listening.
+pub fn subscribe(
+    api_url: &str,
+    on_open: impl FnOnce(),
+    mut on_event: impl FnMut(ServerEvent) -> bool,
+) -> Result<(), ApiError> {
+    let agent = ureq::AgentBuilder::new()
+        .user_agent(USER_AGENT)
+        .timeout_connect(CONNECT_TIMEOUT)
+        .timeout_read(READ_TIMEOUT)
+        .build();
+    let resp = agent
+        .get(&format!("{api_url}/api/events"))
+        .set("Accept", "text/event-stream")
+        .call()?;
+
+    if !resp.content_type().starts_with("text/event-stream") {
+        return Err(ApiError::Status {
+            code: 501,
+            message: format!("unexpected content type {}", resp.content_type()),
+        });
+    }
+
+    on_open();
+    let mut parser = EventParser::default();
+    for line in BufReader::new(resp.into_reader()).lines() {
+        if let Some(event) = parser.feed(&line?)
+            && !on_event(event)
+        {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// Incremental `text/event-stream` parser, fed one line at a time
+#[derive(Debug, Default)]
+struct EventParser {
+    event: String,
+    data: Vec<String>,
+}
+
+impl EventParser {
+    /// Feed one line (without its newline), returning an event when a blank
+    /// line completes one
+    fn feed(&mut self, line: &str) -> Option<ServerEvent> {
+        if line.is_empty() {
+            let event = std::mem::take(&mut self.event);
+            let data = std::mem::take(&mut self.data).join("\n");
+            return parse_event(&event, &data);
+        }
+        if line.starts_with(':') {
+            return None;
+        }
+        let (field, value) = line.split_once(':').unwrap_or((line, ""));
+        let value = value.strip_prefix(' ').unwrap_or(value);
+        match field {
+            "event" => self.event = value.to_string(),
+            "data" => self.data.push(value.to_string()),
+            _ => {}
+        }
+        None
+    }
+}
+
+fn parse_event(event: &str, data: &str) -> Option<ServerEvent> {
+    match event {
+        "download" => serde_json::from_str(data).ok().map(ServerEvent::Download),
+        "delete" => serde_json::from_str::<DeletePayload>(data)
+            .ok()
+            .map(|p| ServerEvent::Deleted(p.id)),
+        "log" => serde_json::from_str::<LogPayload>(data)
+            .ok()
+            .map(|p| ServerEvent::Log(p.line)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::DownloadStatus;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+
+    /// Serve one scripted HTTP response on a local port, returning its base URL
+    fn stand_in_server(status: &str, content_type: &str, body: &str) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let response = format!(
+            "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nConnection: close\r\n\r\n{body}"
+        );
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buf = [0u8; 1024];
+            let _ = stream.read(&mut buf);
+            stream.write_all(response.as_bytes()).unwrap();
+        });
+        format!("http://{addr}")
+    }
+
+    #[test]
+    fn test_parser_multiline_data_and_comments() {
+        let mut parser = EventParser::default();
+        assert_eq!(parser.feed(": keepalive"), None);
+        assert_eq!(parser.feed("event: log"), None);
+        assert_eq!(parser.feed(r#"data: {"line":"#), None);
+        assert_eq!(parser.feed(r#"data: "hello"}"#), None);
+        assert_eq!(parser.feed(""), Some(ServerEvent::Log("hello".into())));
+    }
+
+    #[test]
+    fn test_parser_ignores_unknown_events() {
+        let mut parser = EventParser::default();
+        parser.feed("event: ping");
+        parser.feed("data: {}");
+        assert_eq!(parser.feed(""), None);
+        // State is reset for the next event
+        parser.feed("event: delete");
+        parser.feed(r#"data: {"id":7}"#);
+        assert_eq!(parser.feed(""), Some(ServerEvent::Deleted(7)));
+    }
+
+    #[test]
+    fn test_subscribe_scripted_events() {
+        let body = concat!(
+            ": connected\n\n",
+            "event: download\n",
+            r#"data: {"id":3,"collection":"yt","createdAt":"2026-10-01T00:00:00.000Z","downloadedAt":null,"priority":"normal","status":"downloading","title":null,"url":"https://youtube.com/watch?v=x","errorMessage":null}"#,
+            "\n\n",
+            "event: log\n",
+            r#"data: {"line":"downloading: https://youtube.com/watch?v=x (id: 3)"}"#,
+            "\n\n",
+            "event: delete\n",
+            r#"data: {"id":2}"#,
+            "\n\n",
+        );
+        let url = stand_in_server("200 OK", "text/event-stream", body);
+
+        let mut opened = false;
+        let mut events = Vec::new();
+        subscribe(
+            &url,
+            || opened = true,
+            |e| {
+                events.push(e);
+                true
+            },
+        )
+        .unwrap();
+
+        assert!(opened);
+        assert_eq!(events.len(), 3);
+        match &events[0] {
+            ServerEvent::Download(d) => {
+                assert_eq!(d.id, 3);
+                assert_eq!(d.status, DownloadStatus::Downloading);
+            }
+            other => panic!("expected download event, got {other:?}"),
+        }
+        assert_eq!(
+            events[1],
+            ServerEvent::Log("downloading: https://youtube.com/watch?v=x (id: 3)".into())
+        );
+        assert_eq!(events[2], ServerEvent::Deleted(2));
+    }
+
+    #[test]
+    fn test_subscribe_stops_when_callback_declines() {
+        let body = "event: delete\ndata: {\"id\":1}\n\nevent: delete\ndata: {\"id\":2}\n\n";
+        let url = stand_in_server("200 OK", "text/event-stream", body);
+
+        let mut events = Vec::new();
+        subscribe(
+            &url,
+            || {},
+            |e| {
+                events.push(e);
+                false
+            },
+        )
+        .unwrap();
+        assert_eq!(events, vec![ServerEvent::Deleted(1)]);
+    }
+
+    #[test]
+    fn test_subscribe_unsupported_server() {
+        let url = stand_in_server("404 Not Found", "text/plain", "404 Not Found");
+        let mut opened = false;
+        let err = subscribe(&url, || opened = true, |_| true).unwrap_err();
+        assert!(!opened);
+        assert!(is_unsupported(&err));
+
+        let url = stand_in_server("200 OK", "text/html", "<html></html>");
+        let err = subscribe(&url, || {}, |_| true).unwrap_err();
+        assert!(is_unsupported(&err));
+    }
+
+    #[test]
+    fn test_subscribe_unreachable_is_not_unsupported() {
+        let err = subscribe("http://127.0.0.1:19999", || {}, |_| true).unwrap_err();
+        assert!(!is_unsupported(&err));
+    }
+}
@@ -0,0 +1,172 @@
+//! Exporting the download list as CSV, JSON or a plain URL list.
+
+use crate::types::Download;
+
+/// File formats the download list can be exported as
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+    Urls,
+}
+
+/// CSV columns, named as in the server's JSON
+const CSV_COLUMNS: &[&str] = &[
+    "id",
+    "collection",
+    "createdAt",
+    "downloadedAt",
+    "priority",
+    "status",
+    "title",
+    "url",
+    "errorMessage",
+];
+
+impl ExportFormat {
+    pub const ALL: &[ExportFormat] = &[ExportFormat::Csv, ExportFormat::Json, ExportFormat::Urls];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "CSV",
+            ExportFormat::Json => "JSON",
+            ExportFormat::Urls => "URL list",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+            ExportFormat::Urls => "txt",
+        }
+    }
+
+    /// The file contents for these downloads, in the order given
+    pub fn render(self, downloads: &[&Download]) -> String {
+        match self {
+            ExportFormat::Csv => to_csv(downloads),
+            ExportFormat::Json => {
+                let mut json = serde_json::to_string_pretty(downloads).unwrap_or_default();
+                json.push('\n');
+                json
+            }
+            ExportFormat::Urls => downloads.iter().map(|d| format!("{}\n", d.url)).collect(),
+        }
+    }
+}
+
+fn to_csv(downloads: &[&Download]) -> String {
+    let mut out = CSV_COLUMNS.join(",");
+    out.push_str("\r\n");
+    for dl in downloads {
+        let fields = [
+            dl.id.to_string(),
+            dl.collection.clone(),
+            dl.created_at.map(|t| t.to_iso()).unwrap_or_default(),
+            dl.downloaded_at.map(|t| t.to_iso()).unwrap_or_default(),
+            dl.priority.to_string(),
+            dl.status.to_string(),
+            dl.title.clone().unwrap_or_default(),
+            dl.url.clone(),
+            dl.error_message.clone().unwrap_or_default(),
+        ];
+        let row: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+        out.push_str(&row.join(","));
+        out.push_str("\r\n");
+    }
+    out
+}
+
+/// Quote a field if it contains a delimiter, quote or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time::Timestamp;
+
+    fn at(text: &str) -> Timestamp {
+        Timestamp::parse(text).unwrap()
+    }
+
+    fn downloads() -> Vec<Download> {
+        vec![
+            Download {
+                id: 2,
+                collection: "yt".into(),
+                created_at: Some(at("2024-01-02T00:00:00Z")),
+                priority: "normal".into(),
+                status: "error".into(),
+                title: Some("Talk, \"live\"".into()),
+                url: "https://youtube.com/watch?v=2".into(),
+                error_message: Some("ERROR: gone\nretry later".into()),
+                ..Default::default()
+            },
+            Download {
+                id: 1,
+                collection: "yt".into(),
+                created_at: Some(at("2024-01-01T00:00:00Z")),
+                downloaded_at: Some(at("2024-01-01T00:05:00Z")),
+                priority: "normal".into(),
+                status: "success".into(),
+                url: "https://youtube.com/watch?v=1".into(),
+                ..Default::default()
+            },
+        ]
+    }
+
+    #[test]
+    fn test_render_csv() {
+        let list = downloads();
+        let refs: Vec<&Download> = list.iter().collect();
+        let csv = ExportFormat::Csv.render(&refs);
+        let lines: Vec<&str> = csv.split("\r\n").collect();
+        assert_eq!(
+            lines[0],
+            "id,collection,createdAt,downloadedAt,priority,status,title,url,errorMessage"
+        );
+        assert_eq!(
+            lines[1],
+            "2,yt,2024-01-02T00:00:00.000Z,,normal,error,\"Talk, \"\"live\"\"\",\
+             https://youtube.com/watch?v=2,\"ERROR: gone\nretry later\""
+        );
+        assert_eq!(
+            lines[2],
+            "1,yt,2024-01-01T00:00:00.000Z,2024-01-01T00:05:00.000Z,normal,success,,\
+             https://youtube.com/watch?v=1,"
+        );
+    }
+
+    #[test]
+    fn test_render_json_uses_server_field_names() {
+        let list = downloads();
+        let json = ExportFormat::Json.render(&[&list[1]]);
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value[0]["id"], 1);
+        assert_eq!(value[0]["createdAt"], "2024-01-01T00:00:00.000Z");
+        assert_eq!(value[0]["downloadedAt"], "2024-01-01T00:05:00.000Z");
+        assert!(value[0]["errorMessage"].is_null());
+
+        // Reads back as the same downloads
+        let parsed: Vec<Download> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, vec![list[1].clone()]);
+    }
+
+    #[test]
+    fn test_render_urls() {
+        let list = downloads();
+        let refs: Vec<&Download> = list.iter().collect();
+        assert_eq!(
+            ExportFormat::Urls.render(&refs),
+            "https://youtube.com/watch?v=2\nhttps://youtube.com/watch?v=1\n"
+        );
+        assert_eq!(ExportFormat::Urls.render(&[]), "");
+    }
+}
@@ -0,0 +1,116 @@
+//! Header row for a group of downloads in the Downloads list.
+//!
+//! Like download rows, headers are rebound as the list changes, and their
+//! buttons trigger `app.toggle-group` and `app.group-action` with the group
+//! key as target rather than connecting signal handlers.
+
+use gtk::gio;
+use gtk::glib;
+use gtk::prelude::*;
+use gtk::subclass::prelude::*;
+
+use crate::types::GroupHeader;
+
+mod imp {
+    use super::*;
+
+    #[derive(Default)]
+    pub struct GroupRow {
+        pub expand_button: gtk::Button,
+        pub title_label: gtk::Label,
+        pub count_label: gtk::Label,
+        pub actions_button: gtk::MenuButton,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for GroupRow {
+        const NAME: &'static str = "DlmGroupRow";
+        type Type = super::GroupRow;
+        type ParentType = gtk::Box;
+    }
+
+    impl ObjectImpl for GroupRow {
+        fn constructed(&self) {
+            self.parent_constructed();
+            let obj = self.obj();
+            obj.set_orientation(gtk::Orientation::Horizontal);
+            obj.set_spacing(6);
+            obj.set_margin_top(6);
+            obj.set_margin_bottom(2);
+            obj.set_margin_start(6);
+            obj.set_margin_end(12);
+
+            self.expand_button.set_css_classes(&["flat", "circular"]);
+            self.expand_button.set_valign(gtk::Align::Center);
+
+            self.title_label.set_halign(gtk::Align::Start);
+            self.title_label
+                .set_ellipsize(gtk::pango::EllipsizeMode::End);
+            self.title_label.set_css_classes(&["heading"]);
+
+            self.count_label.set_halign(gtk::Align::Start);
+            self.count_label.set_hexpand(true);
+            self.count_label.set_css_classes(&["dim-label", "caption"]);
+
+            self.actions_button.set_icon_name("view-more-symbolic");
+            self.actions_button
+                .set_tooltip_text(Some("Actions for this group"));
+            self.actions_button.set_css_classes(&["flat", "circular"]);
+            self.actions_button.set_valign(gtk::Align::Center);
+
+            obj.append(&self.expand_button);
+            obj.append(&self.title_label);
+            obj.append(&self.count_label);
+            obj.append(&self.actions_button);
+        }
+    }
+
+    impl WidgetImpl for GroupRow {}
+    impl BoxImpl for GroupRow {}
+}
+
+glib::wrapper! {
+    pub struct GroupRow(ObjectSubclass<imp::GroupRow>)
+        @extends gtk::Box, gtk::Widget,
+        @implements gtk::Accessible, gtk::Buildable, gtk::ConstraintTarget, gtk::Orientable;
+}
+
+impl Default for GroupRow {
+    fn default() -> Self {
+        glib::Object::new()
+    }
+}
+
+impl GroupRow {
+    /// Show a group header in this row
+    pub fn bind(&self, header: &GroupHeader) {
+        let imp = self.imp();
+        let key = header.key.to_variant();
+
+        let (icon, tooltip) = if header.collapsed {
+            ("pan-end-symbolic", "Expand")
+        } else {
+            ("pan-down-symbolic", "Collapse")
+        };
+        imp.expand_button.set_icon_name(icon);
+        imp.expand_button.set_tooltip_text(Some(tooltip));
+        imp.expand_button.set_action_target_value(Some(&key));
+        imp.expand_button.set_action_name(Some("app.toggle-group"));
+
+        imp.title_label.set_text(&header.title);
+        imp.count_label.set_text(&header.count.to_string());
+
+        // One entry per bulk action that applies to some of the group
+        let menu = gio::Menu::new();
+        for (action, count) in header.action_counts.iter().filter(|(_, n)| *n > 0) {
+            let item = gio::MenuItem::new(Some(&format!("{} ({count})", action.label())), None);
+            item.set_action_and_target_value(
+                Some("app.group-action"),
+                Some(&(action.name(), header.key.as_str()).to_variant()),
+            );
+            menu.append_item(&item);
+        }
+        imp.actions_button.set_sensitive(menu.n_items() > 0);
+        imp.actions_button.set_menu_model(Some(&menu));
+    }
+}
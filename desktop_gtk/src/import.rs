@@ -0,0 +1,450 @@
+//! Importing URLs from backlog files: plain text, CSV, browser bookmark
+//! exports (Netscape bookmark HTML) and OPML.
+//!
+//! CSV files offer a choice of column, bookmark and OPML files a choice of
+//! folder; plain text has nothing to choose.
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use crate::links;
+use crate::types::is_web_url;
+
+/// Largest file read for import
+pub const MAX_FILE_BYTES: u64 = 16 * 1024 * 1024;
+
+/// A link in a bookmark or OPML tree
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TreeLink {
+    /// Folder names from the root down
+    pub folder: Vec<String>,
+    pub url: String,
+}
+
+/// The contents of an import file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    /// Plain text or link files
+    Urls(Vec<String>),
+    /// CSV, with a header row if the first row holds no URLs
+    Table {
+        header: Option<Vec<String>>,
+        rows: Vec<Vec<String>>,
+    },
+    /// Bookmark HTML or OPML
+    Tree(Vec<TreeLink>),
+}
+
+impl Source {
+    /// Read a file's contents in the format its name and contents suggest
+    pub fn parse(path: &Path, contents: &str) -> Source {
+        let ext = path
+            .extension()
+            .map(|e| e.to_string_lossy().to_ascii_lowercase());
+        let head = contents
+            .chars()
+            .take(1024)
+            .collect::<String>()
+            .to_ascii_lowercase();
+
+        if head.contains("<opml") {
+            Source::Tree(parse_opml(contents))
+        } else if head.contains("netscape-bookmark-file")
+            || matches!(ext.as_deref(), Some("html" | "htm"))
+        {
+            Source::Tree(parse_bookmarks(contents))
+        } else if matches!(ext.as_deref(), Some("csv" | "tsv")) {
+            let delimiter = if ext.as_deref() == Some("tsv") {
+                '\t'
+            } else {
+                ','
+            };
+            let mut rows = parse_csv(contents, delimiter);
+            let header = match rows.first() {
+                Some(first) if rows.len() > 1 && !first.iter().any(|c| is_web_url(c.trim())) => {
+                    Some(rows.remove(0))
+                }
+                _ => None,
+            };
+            Source::Table { header, rows }
+        } else {
+            Source::Urls(links::urls_from_file(path, contents))
+        }
+    }
+
+    /// What the user picks between: CSV columns, or folders with "All
+    /// folders" first. Empty when there's nothing to choose.
+    pub fn choices(&self) -> Vec<String> {
+        match self {
+            Source::Urls(_) => Vec::new(),
+            Source::Table { header, rows } => {
+                let width = rows
+                    .iter()
+                    .chain(header.iter())
+                    .map(Vec::len)
+                    .max()
+                    .unwrap_or(0);
+                (0..width)
+                    .map(|i| match header.as_ref().and_then(|h| h.get(i)) {
+                        Some(name) if !name.trim().is_empty() => name.trim().to_string(),
+                        _ => format!("Column {}", i + 1),
+                    })
+                    .collect()
+            }
+            Source::Tree(links) => {
+                let mut folders = vec!["All folders".to_string()];
+                for link in links {
+                    // Each ancestor too, so a parent folder can be picked
+                    for depth in 1..=link.folder.len() {
+                        let path = link.folder[..depth].join(" / ");
+                        if !folders.contains(&path) {
+                            folders.push(path);
+                        }
+                    }
+                }
+                folders
+            }
+        }
+    }
+
+    /// The choice to start with: the first column holding URLs, or all folders
+    pub fn default_choice(&self) -> usize {
+        match self {
+            Source::Table { rows, .. } => (0..self.choices().len())
+                .find(|&col| {
+                    rows.iter()
+                        .any(|row| row.get(col).is_some_and(|c| is_web_url(c.trim())))
+                })
+                .unwrap_or(0),
+            _ => 0,
+        }
+    }
+
+    /// The URLs for a choice, in file order and without duplicates
+    pub fn urls(&self, choice: usize) -> Vec<String> {
+        let urls: Vec<String> = match self {
+            Source::Urls(urls) => urls.clone(),
+            Source::Table { rows, .. } => rows
+                .iter()
+                .filter_map(|row| row.get(choice))
+                .map(|cell| cell.trim().to_string())
+                .filter(|cell| is_web_url(cell))
+                .collect(),
+            Source::Tree(links) => {
+                let choices = self.choices();
+                let folder = choices.get(choice).filter(|_| choice > 0);
+                links
+                    .iter()
+                    .filter(|link| match folder {
+                        None => true,
+                        Some(folder) => {
+                            let path = link.folder.join(" / ");
+                            path == *folder || path.starts_with(&format!("{folder} / "))
+                        }
+                    })
+                    .map(|link| link.url.clone())
+                    .collect()
+            }
+        };
+        let mut seen: HashSet<String> = HashSet::with_capacity(urls.len());
+        urls.into_iter()
+            .filter(|url| seen.insert(url.clone()))
+            .collect()
+    }
+}
+
+/// Split CSV into rows of fields, handling quoted fields with embedded
+/// delimiters, newlines and doubled quotes
+fn parse_csv(contents: &str, delimiter: char) -> Vec<Vec<String>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = contents.trim_start_matches('\u{feff}').chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' if quoted => quoted = false,
+            '"' if field.is_empty() => quoted = true,
+            '\r' if !quoted => {}
+            '\n' if !quoted => {
+                row.push(std::mem::take(&mut field));
+                if row.iter().any(|f| !f.is_empty()) {
+                    rows.push(std::mem::take(&mut row));
+                } else {
+                    row.clear();
+                }
+            }
+            c if c == delimiter && !quoted => row.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    row.push(field);
+    if row.iter().any(|f| !f.is_empty()) {
+        rows.push(row);
+    }
+    rows
+}
+
+/// Tags of an HTML or XML document in order, each as the text between `<`
+/// and `>`, paired with the text that follows it up to the next tag
+fn tags(contents: &str) -> impl Iterator<Item = (&str, &str)> {
+    contents.split('<').skip(1).filter_map(|chunk| {
+        let (tag, text) = chunk.split_once('>')?;
+        Some((tag.trim(), text))
+    })
+}
+
+/// Name of a tag, lowercased, with a leading `/` for closing tags
+fn tag_name(tag: &str) -> String {
+    tag.split(|c: char| c.is_whitespace() || (c == '/' && !tag.starts_with('/')))
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase()
+}
+
+/// Value of an attribute in a tag, with character references decoded
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let lower = tag.to_ascii_lowercase();
+    let mut pos = 0;
+    while let Some(found) = lower[pos..].find(name) {
+        let start = pos + found;
+        pos = start + name.len();
+        // Whole attribute names only (`url` shouldn't match `xmlUrl`)
+        if !tag[..start].ends_with(char::is_whitespace) {
+            continue;
+        }
+        let Some(rest) = tag[pos..].trim_start().strip_prefix('=') else {
+            continue;
+        };
+        let rest = rest.trim_start();
+        let value = match rest.chars().next() {
+            Some(quote @ ('"' | '\'')) => rest[1..].split(quote).next().unwrap_or_default(),
+            _ => rest.split(char::is_whitespace).next().unwrap_or_default(),
+        };
+        return Some(links::decode_entities(value.trim()));
+    }
+    None
+}
+
+/// Links in a Netscape bookmark file (the HTML export of every major browser),
+/// where `<H3>` names the folder whose `<DL>` list follows
+fn parse_bookmarks(contents: &str) -> Vec<TreeLink> {
+    let mut links = Vec::new();
+    let mut folder: Vec<String> = Vec::new();
+    // Whether each open <DL> belongs to a named folder
+    let mut lists: Vec<bool> = Vec::new();
+    let mut pending: Option<String> = None;
+    let mut heading: Option<String> = None;
+
+    for (tag, text) in tags(contents) {
+        match tag_name(tag).as_str() {
+            "h3" => heading = Some(text.to_string()),
+            "/h3" => {
+                if let Some(h) = heading.take() {
+                    pending = Some(links::decode_entities(h.trim()));
+                }
+            }
+            "dl" => {
+                let named = pending.take();
+                lists.push(named.is_some());
+                folder.extend(named);
+            }
+            "/dl" if lists.pop() == Some(true) => {
+                folder.pop();
+            }
+            "a" => {
+                if let Some(url) = attribute(tag, "href").filter(|u| is_web_url(u)) {
+                    links.push(TreeLink {
+                        folder: folder.clone(),
+                        url,
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+    links
+}
+
+/// Links in an OPML outline. Outlines without a URL are folders; an entry's
+/// `url` is preferred, then its `htmlUrl` page, then its `xmlUrl` feed.
+fn parse_opml(contents: &str) -> Vec<TreeLink> {
+    let mut links = Vec::new();
+    // Open outlines: folder name, or None for an entry with children
+    let mut open: Vec<Option<String>> = Vec::new();
+
+    for (tag, _) in tags(contents) {
+        match tag_name(tag).as_str() {
+            "outline" => {
+                let url = ["url", "htmlurl", "xmlurl"]
+                    .iter()
+                    .filter_map(|name| attribute(tag, name))
+                    .find(|u| is_web_url(u));
+                let folder = open.iter().flatten().cloned().collect();
+                let self_closing = tag.ends_with('/');
+                match url {
+                    Some(url) => {
+                        links.push(TreeLink { folder, url });
+                        if !self_closing {
+                            open.push(None);
+                        }
+                    }
+                    None if !self_closing => {
+                        let name = attribute(tag, "text")
+                            .or_else(|| attribute(tag, "title"))
+                            .unwrap_or_default();
+                        open.push(Some(name));
+                    }
+                    None => {}
+                }
+            }
+            "/outline" => {
+                open.pop();
+            }
+            _ => {}
+        }
+    }
+    links
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_text() {
+        let source = Source::parse(
+            Path::new("backlog.txt"),
+            "https://a.example/1\nnote: https://a.example/2\nhttps://a.example/1\n",
+        );
+        assert!(source.choices().is_empty());
+        assert_eq!(
+            source.urls(0),
+            vec!["https://a.example/1", "https://a.example/2"]
+        );
+    }
+
+    #[test]
+    fn test_parse_csv_with_header() {
+        let csv = "\u{feff}title,link,notes\r\n\
+                   \"Talk, part 1\",https://youtube.com/watch?v=1,\"said \"\"hi\"\"\"\r\n\
+                   Other,not a url,\"multi\nline\"\r\n\
+                   \r\n\
+                   Last,https://youtube.com/watch?v=2\r\n";
+        let source = Source::parse(Path::new("list.CSV"), csv);
+        let Source::Table { header, rows } = &source else {
+            panic!("expected a table");
+        };
+        assert_eq!(
+            header.as_deref(),
+            Some(&["title", "link", "notes"].map(String::from)[..])
+        );
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0][2], "said \"hi\"");
+        assert_eq!(rows[1][2], "multi\nline");
+
+        assert_eq!(source.choices(), vec!["title", "link", "notes"]);
+        assert_eq!(source.default_choice(), 1);
+        assert_eq!(
+            source.urls(1),
+            vec![
+                "https://youtube.com/watch?v=1",
+                "https://youtube.com/watch?v=2"
+            ]
+        );
+        assert!(source.urls(0).is_empty());
+    }
+
+    #[test]
+    fn test_parse_csv_without_header() {
+        let source = Source::parse(
+            Path::new("l.tsv"),
+            "1\thttps://a.example/x\n2\thttps://a.example/y",
+        );
+        assert_eq!(source.choices(), vec!["Column 1", "Column 2"]);
+        assert_eq!(source.default_choice(), 1);
+        assert_eq!(source.urls(1).len(), 2);
+    }
+
+    #[test]
+    fn test_parse_bookmarks() {
+        let html = r#"<!DOCTYPE NETSCAPE-Bookmark-file-1>
+<TITLE>Bookmarks</TITLE>
+<DL><p>
+    <DT><H3 ADD_DATE="1">Bookmarks bar</H3>
+    <DL><p>
+        <DT><A HREF="https://example.com/top">Top</A>
+        <DT><H3>Music &amp; Talks</H3>
+        <DL><p>
+            <DT><A HREF="https://youtube.com/watch?v=1&amp;t=5" ADD_DATE="2">One</A>
+            <DT><A HREF="place:sort=8">Smart folder</A>
+        </DL><p>
+        <DT><A HREF="https://example.com/after">After</A>
+    </DL><p>
+    <DT><A HREF="https://example.com/loose">Loose</A>
+</DL>"#;
+        let source = Source::parse(Path::new("bookmarks.html"), html);
+        assert_eq!(
+            source.choices(),
+            vec![
+                "All folders",
+                "Bookmarks bar",
+                "Bookmarks bar / Music & Talks"
+            ]
+        );
+        assert_eq!(source.urls(0).len(), 4);
+        assert_eq!(
+            source.urls(1),
+            vec![
+                "https://example.com/top",
+                "https://youtube.com/watch?v=1&t=5",
+                "https://example.com/after"
+            ]
+        );
+        assert_eq!(source.urls(2), vec!["https://youtube.com/watch?v=1&t=5"]);
+    }
+
+    #[test]
+    fn test_parse_opml() {
+        let opml = r#"<?xml version="1.0"?>
+<opml version="2.0"><body>
+  <outline text="Podcasts">
+    <outline text="Show" type="rss" xmlUrl="https://example.com/feed.xml"/>
+    <outline text="Site" htmlUrl="https://example.com/site" xmlUrl="https://example.com/site.xml"/>
+  </outline>
+  <outline text="Talk" url="https://youtube.com/watch?v=1"></outline>
+</body></opml>"#;
+        let source = Source::parse(Path::new("subs.xml"), opml);
+        assert_eq!(source.choices(), vec!["All folders", "Podcasts"]);
+        assert_eq!(
+            source.urls(0),
+            vec![
+                "https://example.com/feed.xml",
+                "https://example.com/site",
+                "https://youtube.com/watch?v=1"
+            ]
+        );
+        assert_eq!(source.urls(1).len(), 2);
+    }
+
+    #[test]
+    fn test_attribute() {
+        let tag =
+            r#"outline text='A &amp; B' xmlUrl="https://x.example/f" url=https://x.example/u"#;
+        assert_eq!(attribute(tag, "text").as_deref(), Some("A & B"));
+        assert_eq!(
+            attribute(tag, "xmlurl").as_deref(),
+            Some("https://x.example/f")
+        );
+        assert_eq!(
+            attribute(tag, "url").as_deref(),
+            Some("https://x.example/u")
+        );
+        assert_eq!(attribute(tag, "htmlurl"), None);
+    }
+}
@@ -0,0 +1,283 @@
+//! Pulling http(s) URLs out of dropped or pasted text, HTML and link files.
+
+use std::path::Path;
+
+use crate::types::is_web_url;
+
+/// Characters that end a URL in running text
+fn is_url_terminator(c: char) -> bool {
+    c.is_whitespace() || matches!(c, '"' | '\'' | '<' | '>' | '`')
+}
+
+/// Every http(s) URL in `text`, in order and without duplicates
+pub fn extract_urls(text: &str) -> Vec<String> {
+    let mut urls: Vec<String> = Vec::new();
+    let mut rest = text;
+
+    while let Some(start) = [rest.find("http://"), rest.find("https://")]
+        .into_iter()
+        .flatten()
+        .min()
+    {
+        let candidate = &rest[start..];
+        let mut end = candidate.find(is_url_terminator).unwrap_or(candidate.len());
+        // Comma-separated lists: `https://a/1,https://a/2`
+        if let Some(next) = [candidate.find(",http://"), candidate.find(",https://")]
+            .into_iter()
+            .flatten()
+            .min()
+        {
+            end = end.min(next);
+        }
+        let url = trim_trailing_punctuation(&candidate[..end]);
+        if is_web_url(url) && !urls.iter().any(|u| u == url) {
+            urls.push(url.to_string());
+        }
+        rest = &candidate[end..];
+    }
+    urls
+}
+
+/// Drop sentence punctuation after a URL, and closing brackets that aren't
+/// part of it (`(see https://example.com/a)` vs `.../Rust_(language)`)
+fn trim_trailing_punctuation(url: &str) -> &str {
+    let mut url = url;
+    loop {
+        let Some(last) = url.chars().last() else {
+            return url;
+        };
+        let unbalanced = |open: char| url.matches(open).count() < url.matches(last).count();
+        let trim = match last {
+            '.' | ',' | ';' | ':' | '!' | '?' | '*' | '_' | '~' => true,
+            ')' => unbalanced('('),
+            ']' => unbalanced('['),
+            '}' => unbalanced('{'),
+            _ => false,
+        };
+        if !trim {
+            return url;
+        }
+        url = &url[..url.len() - last.len_utf8()];
+    }
+}
+
+/// Every http(s) URL in an HTML snippet: link targets (`href`) first, then
+/// any URLs in the text itself
+pub fn urls_from_html(html: &str) -> Vec<String> {
+    let mut urls = extract_hrefs(html);
+    for url in extract_urls(&decode_entities(html)) {
+        if !urls.contains(&url) {
+            urls.push(url);
+        }
+    }
+    urls
+}
+
+/// Text of copied HTML. Some browsers offer `text/html` as UTF-16 with a
+/// byte order mark rather than UTF-8.
+pub fn html_from_bytes(data: &[u8]) -> String {
+    let utf16 = |data: &[u8], decode: fn([u8; 2]) -> u16| {
+        let units: Vec<u16> = data
+            .chunks_exact(2)
+            .map(|pair| decode([pair[0], pair[1]]))
+            .collect();
+        String::from_utf16_lossy(&units)
+    };
+    match data {
+        [0xff, 0xfe, rest @ ..] => utf16(rest, u16::from_le_bytes),
+        [0xfe, 0xff, rest @ ..] => utf16(rest, u16::from_be_bytes),
+        _ => String::from_utf8_lossy(data).into_owned(),
+    }
+}
+
+/// The http(s) targets of `href` attributes, in order and without duplicates
+fn extract_hrefs(html: &str) -> Vec<String> {
+    let lower = html.to_ascii_lowercase();
+    let mut urls: Vec<String> = Vec::new();
+    let mut pos = 0;
+
+    while let Some(found) = lower[pos..].find("href") {
+        let after = pos + found + "href".len();
+        pos = after;
+        let rest = html[after..].trim_start();
+        let Some(rest) = rest.strip_prefix('=') else {
+            continue;
+        };
+        let rest = rest.trim_start();
+        let value = match rest.chars().next() {
+            Some(quote @ ('"' | '\'')) => rest[1..].split(quote).next().unwrap_or_default(),
+            _ => rest
+                .split(|c: char| c.is_whitespace() || c == '>')
+                .next()
+                .unwrap_or_default(),
+        };
+        let url = decode_entities(value.trim());
+        if is_web_url(&url) && !urls.contains(&url) {
+            urls.push(url);
+        }
+    }
+    urls
+}
+
+/// Decode the character references that show up in URLs and link text
+pub fn decode_entities(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let decoded = rest.find(';').and_then(|semi| {
+            let ch = match &rest[1..semi] {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                name => name
+                    .strip_prefix("#x")
+                    .or_else(|| name.strip_prefix("#X"))
+                    .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                    .or_else(|| name.strip_prefix('#').and_then(|dec| dec.parse().ok()))
+                    .and_then(char::from_u32),
+            };
+            ch.map(|ch| (ch, semi))
+        });
+        match decoded {
+            Some((ch, semi)) => {
+                out.push(ch);
+                rest = &rest[semi + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// URLs in a dropped file: the `URL=` entry of `.url` and `.desktop` link
+/// files, or every URL in anything else
+pub fn urls_from_file(path: &Path, contents: &str) -> Vec<String> {
+    let ext = path
+        .extension()
+        .map(|e| e.to_string_lossy().to_ascii_lowercase());
+    match ext.as_deref() {
+        Some("url" | "desktop") => contents
+            .lines()
+            .filter_map(|line| line.trim().strip_prefix("URL="))
+            .map(str::trim)
+            .filter(|url| is_web_url(url))
+            .map(str::to_string)
+            .collect(),
+        _ => extract_urls(contents),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_urls_from_text() {
+        let text = "check this https://youtube.com/watch?v=abc, and \
+                    (also https://example.com/a.jpg). Dup: https://youtube.com/watch?v=abc!";
+        assert_eq!(
+            extract_urls(text),
+            vec![
+                "https://youtube.com/watch?v=abc",
+                "https://example.com/a.jpg"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_urls_keeps_balanced_brackets() {
+        assert_eq!(
+            extract_urls("see <https://en.wikipedia.org/wiki/Rust_(language)>."),
+            vec!["https://en.wikipedia.org/wiki/Rust_(language)"]
+        );
+        assert_eq!(
+            extract_urls("[video](https://youtu.be/x)"),
+            vec!["https://youtu.be/x"]
+        );
+    }
+
+    #[test]
+    fn test_extract_urls_from_chat_and_lists() {
+        let chat = "**new:** https://youtu.be/x... and <https://example.com/a?b=1>\n\
+                    > https://a.example/1,https://a.example/2, also `https://a.example/3`";
+        assert_eq!(
+            extract_urls(chat),
+            vec![
+                "https://youtu.be/x",
+                "https://example.com/a?b=1",
+                "https://a.example/1",
+                "https://a.example/2",
+                "https://a.example/3",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_urls_from_html() {
+        let html = r#"<p>Watch <a href="https://youtube.com/watch?v=a&amp;t=5">this</a>,
+            <A HREF='https://example.com/b'>that</A>, <a href=https://example.com/c>c</a>
+            <a href="/relative">rel</a> <a href="mailto:x@example.com">mail</a>
+            or https://example.com/d &amp; https://example.com/b.</p>"#;
+        assert_eq!(
+            urls_from_html(html),
+            vec![
+                "https://youtube.com/watch?v=a&t=5",
+                "https://example.com/b",
+                "https://example.com/c",
+                "https://example.com/d",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_html_from_bytes() {
+        assert_eq!(html_from_bytes(b"<a>"), "<a>");
+        let utf16: Vec<u8> = [0xff, 0xfe]
+            .into_iter()
+            .chain("<a>".encode_utf16().flat_map(u16::to_le_bytes))
+            .collect();
+        assert_eq!(html_from_bytes(&utf16), "<a>");
+    }
+
+    #[test]
+    fn test_decode_entities() {
+        assert_eq!(decode_entities("a&amp;b&#38;c&#x26;d"), "a&b&c&d");
+        assert_eq!(decode_entities("AT&T & co &bogus;"), "AT&T & co &bogus;");
+    }
+
+    #[test]
+    fn test_extract_urls_ignores_non_urls() {
+        assert!(extract_urls("no links here, just http:// and https://").is_empty());
+        assert!(extract_urls("").is_empty());
+    }
+
+    #[test]
+    fn test_urls_from_link_files() {
+        let shortcut = "[InternetShortcut]\r\nURL=https://example.com/a\r\n";
+        assert_eq!(
+            urls_from_file(Path::new("Saved.url"), shortcut),
+            vec!["https://example.com/a"]
+        );
+
+        let desktop = "[Desktop Entry]\nType=Link\nName=Video\nURL=https://youtu.be/x\n";
+        assert_eq!(
+            urls_from_file(Path::new("video.desktop"), desktop),
+            vec!["https://youtu.be/x"]
+        );
+
+        let txt = "https://a.example/1\nnotes https://a.example/2\n";
+        assert_eq!(
+            urls_from_file(Path::new("backlog.txt"), txt),
+            vec!["https://a.example/1", "https://a.example/2"]
+        );
+    }
+}
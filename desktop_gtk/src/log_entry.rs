@@ -0,0 +1,294 @@
+//! Lines of the server's `dlm.log`, parsed for the Logs page.
+//!
+//! The server's `Logger` prefixes errors with `[ERROR] ` and debug output with
+//! `[DEBUG] `, and writes everything else unmarked. Download progress starts
+//! with an ISO timestamp in brackets and names downloads as `(id: N)`; other
+//! messages, such as `added … to db`, are bare text.
+//!
+//! Each line is an entry of its own, except that a failed command's `[ERROR] `
+//! line carries its stderr, which can span lines. Up to [`MAX_OUTPUT_LINES`]
+//! lines after it are folded into it unless they look like something the
+//! server logs itself.
+
+use std::ops::Range;
+
+use crate::links;
+use crate::time::Timestamp;
+
+/// Severity of a log line, from its prefix
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum LogLevel {
+    #[default]
+    Info,
+    Debug,
+    Error,
+}
+
+impl LogLevel {
+    /// Name of the text tag lines of this level are shown with
+    pub fn tag_name(&self) -> Option<&'static str> {
+        match self {
+            LogLevel::Info => None,
+            LogLevel::Debug => Some("debug"),
+            LogLevel::Error => Some("error"),
+        }
+    }
+}
+
+/// A line of the server log, with the command output that follows a failed
+/// download. Ranges are byte ranges into `text`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct LogEntry {
+    /// The line as written, then any command output folded in, joined by
+    /// `\n`
+    pub text: String,
+    pub level: LogLevel,
+    /// Time the message starts with
+    pub time: Option<Timestamp>,
+    /// Range of the timestamp, brackets included
+    pub time_range: Option<Range<usize>>,
+    /// Downloads mentioned as `(id: N)`, with the range of the number
+    pub ids: Vec<(i64, Range<usize>)>,
+    /// Ranges of http(s) URLs
+    pub urls: Vec<Range<usize>>,
+    /// Lines of command output folded in after the first
+    pub output_lines: usize,
+}
+
+impl LogEntry {
+    pub fn parse(line: &str) -> Self {
+        let (level, start) = if line.starts_with("[ERROR] ") {
+            (LogLevel::Error, "[ERROR] ".len())
+        } else if line.starts_with("[DEBUG] ") {
+            (LogLevel::Debug, "[DEBUG] ".len())
+        } else {
+            (LogLevel::Info, 0)
+        };
+
+        let (time, time_range) = match leading_time(&line[start..]) {
+            Some((time, len)) => (Some(time), Some(start..start + len)),
+            None => (None, None),
+        };
+
+        let ids = line
+            .match_indices("(id: ")
+            .filter_map(|(i, marker)| {
+                let from = i + marker.len();
+                let digits = line[from..]
+                    .find(|c: char| !c.is_ascii_digit())
+                    .unwrap_or(line.len() - from);
+                let id = line[from..from + digits].parse().ok()?;
+                Some((id, from..from + digits))
+            })
+            .collect();
+
+        let mut urls: Vec<Range<usize>> = links::extract_urls(line)
+            .iter()
+            .flat_map(|url| {
+                line.match_indices(url.as_str())
+                    .map(|(i, u)| i..i + u.len())
+            })
+            .collect();
+        urls.sort_by_key(|r| r.start);
+
+        LogEntry {
+            text: line.to_string(),
+            level,
+            time,
+            time_range,
+            ids,
+            urls,
+            output_lines: 0,
+        }
+    }
+
+    /// Parse a log, folding command output into the error before it
+    pub fn parse_all<'a>(lines: impl IntoIterator<Item = &'a str>) -> Vec<LogEntry> {
+        let mut entries = Vec::new();
+        for line in lines {
+            push_line(&mut entries, line);
+        }
+        entries
+    }
+
+    /// Whether this is a failed command's error, `… (id: N): <stderr>`, with
+    /// room for more of its output
+    fn takes_output(&self) -> bool {
+        self.level == LogLevel::Error
+            && self.output_lines < MAX_OUTPUT_LINES
+            && self
+                .ids
+                .first()
+                .is_some_and(|(_, range)| self.text[range.end..].starts_with("): "))
+    }
+
+    /// Append a line of command output, parsing only that line
+    fn push_output(&mut self, line: &str) {
+        let offset = self.text.len() + 1;
+        let shift = |range: Range<usize>| range.start + offset..range.end + offset;
+        let parsed = LogEntry::parse(line);
+        self.text.push('\n');
+        self.text.push_str(line);
+        self.ids
+            .extend(parsed.ids.into_iter().map(|(id, range)| (id, shift(range))));
+        self.urls.extend(parsed.urls.into_iter().map(shift));
+        self.output_lines += 1;
+    }
+}
+
+/// Lines of command output folded into one error entry
+pub const MAX_OUTPUT_LINES: usize = 20;
+
+/// Starts of the unmarked lines the server logs itself (web.ts, download.ts,
+/// daemon.ts), which never belong to a command's output
+const SERVER_MESSAGES: &[&str] = &[
+    "added ",
+    "urls ",
+    "download deleted from db: ",
+    "Remapped download ",
+    "Migration: ",
+    "Database initialized",
+    "Daemon ",
+    "Main thread: ",
+    "Unknown message type: ",
+    "Reset ",
+    "Worker finished",
+    "Force shutdown",
+    "Shutting down daemon",
+    "[GET] ",
+    "[POST] ",
+    "[PUT] ",
+    "[PATCH] ",
+    "[DELETE] ",
+    "[HEAD] ",
+    "[OPTIONS] ",
+];
+
+/// Whether `line` reads like a message of the server rather than output of
+/// a command: a level prefix, a leading timestamp, a known message, or a
+/// lone URL as logged when adding
+fn is_server_line(line: &str) -> bool {
+    line.starts_with("[ERROR] ")
+        || line.starts_with("[DEBUG] ")
+        || leading_time(line).is_some()
+        || SERVER_MESSAGES.iter().any(|start| line.starts_with(start))
+        || line.ends_with(" already present")
+        || links::extract_urls(line)
+            .first()
+            .is_some_and(|url| url == line)
+}
+
+/// Add a line of the log to `entries`: as output of the last entry if that
+/// is a failed command still taking output and the line isn't the server's
+/// own, otherwise as an entry of its own
+pub fn push_line(entries: &mut Vec<LogEntry>, line: &str) {
+    match entries.last_mut() {
+        Some(last) if last.takes_output() && !is_server_line(line) => last.push_output(line),
+        _ => entries.push(LogEntry::parse(line)),
+    }
+}
+
+/// Time `text` starts with in brackets, and the length of the brackets
+fn leading_time(text: &str) -> Option<(Timestamp, usize)> {
+    let end = text.strip_prefix('[')?.find(']')? + 1;
+    Some((Timestamp::parse(&text[1..end])?, end + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_error_line() {
+        let line = "[ERROR] [2026-10-01T12:00:00.000Z] error: https://example.com/a?error=1 (id: 42): exit code 1";
+        let entry = LogEntry::parse(line);
+        assert_eq!(entry.level, LogLevel::Error);
+        assert_eq!(entry.time, Timestamp::parse("2026-10-01T12:00:00.000Z"));
+        assert_eq!(
+            &line[entry.time_range.clone().unwrap()],
+            "[2026-10-01T12:00:00.000Z]"
+        );
+        assert_eq!(entry.ids.len(), 1);
+        assert_eq!(entry.ids[0].0, 42);
+        assert_eq!(&line[entry.ids[0].1.clone()], "42");
+        assert_eq!(entry.urls.len(), 1);
+        assert_eq!(
+            &line[entry.urls[0].clone()],
+            "https://example.com/a?error=1"
+        );
+    }
+
+    #[test]
+    fn test_parse_info_and_debug_lines() {
+        let info = LogEntry::parse("[2026-10-01T12:00:00.000Z] daemon: starting run");
+        assert_eq!(info.level, LogLevel::Info);
+        assert_eq!(info.time_range, Some(0..26));
+        assert!(info.ids.is_empty() && info.urls.is_empty());
+
+        let debug = LogEntry::parse("[DEBUG] queue: [1, 2] (id: x) (id: 7)");
+        assert_eq!(debug.level, LogLevel::Debug);
+        assert_eq!(debug.time, None);
+        assert_eq!(
+            debug.ids.iter().map(|(id, _)| *id).collect::<Vec<_>>(),
+            vec![7]
+        );
+
+        let plain = LogEntry::parse("No log file found or error reading logs.");
+        assert_eq!(plain.level, LogLevel::Info);
+        assert_eq!(plain.time, None);
+    }
+
+    #[test]
+    fn test_parse_multiline_error() {
+        let entries = LogEntry::parse_all([
+            "[2026-10-01T12:00:00.000Z] daemon: starting run",
+            "[ERROR] [2026-10-01T12:00:01.000Z] error: https://youtu.be/abc (id: 42): ERROR: [youtube] abc: Video unavailable",
+            "  see https://example.com/help",
+            "[youtube] retrying (id: 7)",
+            "[DEBUG] queue: [43]",
+            "[2026-10-01T12:00:02.000Z] daemon: done",
+        ]);
+        assert_eq!(entries.len(), 4);
+        let error = &entries[1];
+        assert_eq!(error.level, LogLevel::Error);
+        assert_eq!(error.output_lines, 2);
+        assert!(
+            error.text.ends_with(
+                "unavailable\n  see https://example.com/help\n[youtube] retrying (id: 7)"
+            )
+        );
+        assert_eq!(
+            error.ids.iter().map(|(id, _)| *id).collect::<Vec<_>>(),
+            vec![42, 7]
+        );
+        assert_eq!(&error.text[error.ids[1].1.clone()], "7");
+        let urls: Vec<&str> = error.urls.iter().map(|r| &error.text[r.clone()]).collect();
+        assert_eq!(
+            urls,
+            vec!["https://youtu.be/abc", "https://example.com/help"]
+        );
+        assert_eq!(entries[2].level, LogLevel::Debug);
+
+        // The server's own lines after an error are info lines of their own
+        let entries = LogEntry::parse_all([
+            "[ERROR] [2026-10-01T12:00:01.000Z] error: a (id: 1): exit code 1",
+            "added https://example.com/b to db",
+            "https://example.com/c",
+            "[GET] http://localhost:8001/api/logs Mozilla localhost",
+            "Daemon worker is ready",
+        ]);
+        assert_eq!(entries.len(), 5);
+        assert!(entries[1..].iter().all(|e| e.level == LogLevel::Info));
+
+        // Only failed commands carry output, and only so much of it
+        let entries = LogEntry::parse_all(["[ERROR] config is invalid", "stray output"]);
+        assert_eq!(entries.len(), 2);
+        let mut lines = vec!["[ERROR] error: a (id: 1): failed"];
+        lines.extend(["more output"; MAX_OUTPUT_LINES + 1]);
+        let entries = LogEntry::parse_all(lines);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].output_lines, MAX_OUTPUT_LINES);
+        assert_eq!(entries[1].level, LogLevel::Info);
+        assert_eq!(LogEntry::parse_all(["stray output", "more"]).len(), 2);
+    }
+}
@@ -5,19 +5,51 @@
 //! and full download management capabilities.
 
 mod api;
+mod backup;
+mod cli;
+mod clipboard;
 mod config;
+mod download_log;
+mod download_object;
+mod download_row;
+mod events;
+mod export;
+mod group_row;
+mod import;
+mod links;
+mod log_entry;
+mod notifications;
+mod scheme;
+mod search;
+mod time;
 mod types;
+mod url_check;
 
 use std::cell::RefCell;
+use std::collections::HashSet;
 use std::rc::Rc;
+use std::sync::mpsc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use adw::prelude::*;
 use glib::clone;
 use gtk::gio;
 use gtk::glib;
 
-use config::{load_settings, save_settings, AppSettings};
-use types::{AppState, LogFilter, RefreshData, SortOrder, StatusFilter, Widgets};
+use api::ApiError;
+use cli::CliOptions;
+use config::{AppSettings, autostart_enabled, load_settings, save_settings, set_autostart};
+use download_object::{DownloadObject, GroupObject, ListEntry};
+use download_row::{DownloadRow, RowContext};
+use events::ServerEvent;
+use group_row::GroupRow;
+use log_entry::LogEntry;
+use time::Timestamp;
+use types::{
+    AppState, ConnectionStatus, Download, DownloadAction, DownloadStatus, Endpoint, Filter,
+    GroupBy, LogFilter, RefreshData, SavedView, SidebarItem, SortKey, SortOrder, StatusFilter,
+    StreamStatus, Widgets,
+};
 
 // ============================================================================
 // Configuration
@@ -25,20 +57,57 @@ use types::{AppState, LogFilter, RefreshData, SortOrder, StatusFilter, Widgets};
 
 pub const APP_ID: &str = "com.brettchalupa.dlm.desktop";
 pub const CONFIG_DIR: &str = ".config/dlm-gtk";
+/// Start hidden in the background, as the autostart entry does
+pub const BACKGROUND_FLAG: &str = "--background";
 const WINDOW_TITLE: &str = "DLM";
 const WINDOW_WIDTH: i32 = 960;
 const WINDOW_HEIGHT: i32 = 700;
 const REFRESH_INTERVAL_SECS: u32 = 3;
-
-const KEYBOARD_SHORTCUTS: &[(&str, &[(&str, &str)])] = &[(
-    "General",
-    &[
-        ("Ctrl+R", "Refresh data"),
-        ("Ctrl+D", "Start downloads"),
-        ("Ctrl+N", "Add URLs"),
-        ("?", "Keyboard shortcuts"),
-    ],
-)];
+const STREAM_POLL_INTERVAL: Duration = Duration::from_millis(250);
+const STREAM_RECONNECT_SECS: u32 = 10;
+
+/// Pages of the window in sidebar order: name (as `--show` takes), title and icon
+const PAGES: &[(&str, &str, &str)] = &[
+    ("downloads", "Downloads", "folder-download-symbolic"),
+    ("errors", "Errors", "dialog-error-symbolic"),
+    ("logs", "Logs", "utilities-terminal-symbolic"),
+    ("config", "Config", "emblem-system-symbolic"),
+];
+
+/// Window width below which the sidebar collapses
+const SIDEBAR_BREAKPOINT_SP: f64 = 640.0;
+
+const KEYBOARD_SHORTCUTS: &[(&str, &[(&str, &str)])] = &[
+    (
+        "General",
+        &[
+            ("Ctrl+R", "Refresh data"),
+            ("Ctrl+D", "Start downloads"),
+            ("Ctrl+N", "Add URLs"),
+            ("Ctrl+O", "Import URLs from a file"),
+            ("Ctrl+F", "Search downloads"),
+            ("Ctrl+S", "Save the current filter as a view"),
+            ("Ctrl+E", "Export downloads"),
+            ("?", "Keyboard shortcuts"),
+            ("Ctrl+Q", "Quit"),
+        ],
+    ),
+    (
+        "Selection",
+        &[
+            ("Ctrl+A", "Select all downloads"),
+            ("Shift+Click", "Select a range"),
+            ("Ctrl+Click", "Add to selection"),
+            ("Escape", "Clear selection"),
+        ],
+    ),
+];
+
+/// Tooltip for the Downloads search entry
+const SEARCH_SYNTAX_HELP: &str = "Words match the title, URL, collection, error or ID.
+status:error  collection:yt  domain:reddit.com  priority:high
+after:2026-10-01  after:-7d  before:yesterday  id:42  title:  url:  error:
+status:pending,error matches either; -word or -\"a phrase\" excludes.";
 
 // ============================================================================
 // CSS
@@ -65,6 +134,9 @@ fn load_css() {
             font-size: 11px;
             font-weight: 600;
         }
+        listview.download-list > row {
+            padding: 0;
+        }
         ",
     );
     gtk::style_context_add_provider_for_display(
@@ -87,59 +159,260 @@ fn trigger_refresh(
     let api_url = settings.borrow().api_url.clone();
     let state = state.clone();
     let widgets = widgets.clone();
-    let settings2 = settings.clone();
+    let settings = settings.clone();
 
     glib::spawn_future_local(async move {
         let data: RefreshData = gio::spawn_blocking(move || api::fetch_all(&api_url))
             .await
-            .unwrap_or_default();
-        apply_refresh_data(&state, &widgets, &settings2, data);
+            .unwrap_or_else(|_| {
+                RefreshData::failed(ApiError::Transport("worker thread panicked".into()))
+            });
+        apply_refresh_data(&state, &widgets, &settings.borrow(), data);
     });
 }
 
 /// Apply fetched data to the app state and update all UI widgets
 fn apply_refresh_data(
     state: &Rc<RefCell<AppState>>,
-    widgets: &Rc<Widgets>,
-    settings: &Rc<RefCell<AppSettings>>,
+    widgets: &Widgets,
+    settings: &AppSettings,
     data: RefreshData,
 ) {
+    // Endpoints that failed keep their last known data
     {
         let mut s = state.borrow_mut();
-        s.counts = data.counts;
-        s.downloads = data.downloads;
-        s.system = data.system;
-        s.logs = data.logs;
-        s.config = data.config;
+        let failed = data.failed_endpoints();
+        if !failed.contains(&Endpoint::Counts) {
+            s.counts = data.counts;
+        }
+        if !failed.contains(&Endpoint::Downloads) {
+            let before = std::mem::replace(&mut s.downloads, data.downloads);
+            notify_transitions(&before, &s.downloads, settings);
+        }
+        if !failed.contains(&Endpoint::System) {
+            s.system = data.system;
+        }
+        if !failed.contains(&Endpoint::Logs) {
+            s.logs = LogEntry::parse_all(data.logs.iter().map(String::as_str));
+        }
+        if !failed.contains(&Endpoint::Config) {
+            s.config = data.config;
+        }
+        s.record_refresh(failed, SystemTime::now());
+    }
+    update_sidebar(state, widgets, &settings.saved_views);
+
+    let s = state.borrow();
+    update_connection_banner(&s, widgets);
+    update_config_view(&s, widgets);
+    update_views(&s, widgets);
+}
+
+/// Send desktop notifications for downloads that finished or failed between
+/// two snapshots
+fn notify_transitions(before: &[Download], after: &[Download], settings: &AppSettings) {
+    let changed = notifications::transitions(before, after);
+    if !changed.is_empty() {
+        notifications::send(notifications::plan(&changed, settings));
     }
+}
+
+/// Redraw everything derived from downloads and logs
+fn update_views(state: &AppState, widgets: &Widgets) {
+    update_stats(state, widgets);
+    update_sidebar_counts(state, widgets);
+    update_logs_view(state, widgets);
+    update_downloads_list(state, widgets);
+    update_errors_list(state, widgets);
+}
+
+// ============================================================================
+// Event stream
+// ============================================================================
+
+/// Messages from the event stream thread to the main loop
+enum StreamMessage {
+    Opened,
+    Event(ServerEvent),
+    Ended(Result<(), ApiError>),
+}
+
+/// Subscribe to the server's event stream on a background thread, applying
+/// pushed updates as they arrive. Any previous subscription is abandoned.
+/// Polling keeps running as the fallback and slows down while the stream is live.
+fn start_event_stream(
+    state: &Rc<RefCell<AppState>>,
+    widgets: &Rc<Widgets>,
+    settings: &Rc<RefCell<AppSettings>>,
+) {
+    let generation = {
+        let mut s = state.borrow_mut();
+        s.stream_generation += 1;
+        s.stream = StreamStatus::Disconnected;
+        s.stream_generation
+    };
+    let api_url = settings.borrow().api_url.clone();
+    let (tx, rx) = mpsc::channel();
+
+    std::thread::spawn(move || {
+        let result = events::subscribe(
+            &api_url,
+            || {
+                let _ = tx.send(StreamMessage::Opened);
+            },
+            // Stop once the main loop has dropped the receiver
+            |event| tx.send(StreamMessage::Event(event)).is_ok(),
+        );
+        let _ = tx.send(StreamMessage::Ended(result));
+    });
+
+    glib::timeout_add_local(
+        STREAM_POLL_INTERVAL,
+        clone!(
+            #[strong]
+            state,
+            #[strong]
+            widgets,
+            #[strong]
+            settings,
+            move || {
+                if state.borrow().stream_generation != generation {
+                    return glib::ControlFlow::Break;
+                }
+
+                // Downloads before this batch of events, once there is one
+                let mut before: Option<Vec<Download>> = None;
+                let mut flow = glib::ControlFlow::Continue;
+                loop {
+                    match rx.try_recv() {
+                        Ok(StreamMessage::Opened) => {
+                            state.borrow_mut().stream = StreamStatus::Live;
+                        }
+                        Ok(StreamMessage::Event(event)) => {
+                            let mut s = state.borrow_mut();
+                            before.get_or_insert_with(|| s.downloads.clone());
+                            s.apply_event(event);
+                        }
+                        Ok(StreamMessage::Ended(result)) => {
+                            handle_stream_end(&state, &widgets, &settings, generation, result);
+                            flow = glib::ControlFlow::Break;
+                            break;
+                        }
+                        Err(mpsc::TryRecvError::Empty) => break,
+                        Err(mpsc::TryRecvError::Disconnected) => {
+                            flow = glib::ControlFlow::Break;
+                            break;
+                        }
+                    }
+                }
+
+                if let Some(before) = before {
+                    let s = state.borrow();
+                    notify_transitions(&before, &s.downloads, &settings.borrow());
+                    update_views(&s, &widgets);
+                }
+                flow
+            }
+        ),
+    );
+}
 
+/// Fall back to polling when a subscription ends, and schedule a reconnect
+/// unless the server doesn't offer a stream at all
+fn handle_stream_end(
+    state: &Rc<RefCell<AppState>>,
+    widgets: &Rc<Widgets>,
+    settings: &Rc<RefCell<AppSettings>>,
+    generation: u64,
+    result: Result<(), ApiError>,
+) {
+    let unsupported = matches!(&result, Err(e) if events::is_unsupported(e));
     {
-        let s = state.borrow();
-        update_stats(&s, widgets);
-        update_logs_view(&s, widgets);
-        update_config_view(&s, widgets);
+        let mut s = state.borrow_mut();
+        s.stream = if unsupported {
+            StreamStatus::Unsupported
+        } else {
+            StreamStatus::Disconnected
+        };
+        // Resync on the next tick in case updates were missed
+        s.ticks_until_refresh = s.ticks_until_refresh.min(1);
+    }
+    if unsupported {
+        return;
     }
-    update_downloads_list(state, widgets, settings);
-    update_errors_list(state, widgets, settings);
+
+    glib::timeout_add_seconds_local_once(
+        STREAM_RECONNECT_SECS,
+        clone!(
+            #[strong]
+            state,
+            #[strong]
+            widgets,
+            #[strong]
+            settings,
+            move || {
+                if state.borrow().stream_generation == generation {
+                    start_event_stream(&state, &widgets, &settings);
+                }
+            }
+        ),
+    );
 }
 
 // ============================================================================
 // UI Update Functions
 // ============================================================================
 
+fn update_connection_banner(state: &AppState, widgets: &Widgets) {
+    let banner = &widgets.connection_banner;
+    match &state.connection {
+        ConnectionStatus::Connecting | ConnectionStatus::Connected => {
+            banner.set_revealed(false);
+        }
+        ConnectionStatus::Degraded(failed) => {
+            let names: Vec<&str> = failed.iter().map(|e| e.label()).collect();
+            banner.set_title(&format!(
+                "Some data could not be loaded: {}",
+                names.join(", ")
+            ));
+            banner.set_revealed(true);
+        }
+        ConnectionStatus::Unreachable { since } => {
+            let retry_secs = state.ticks_until_refresh * REFRESH_INTERVAL_SECS;
+            banner.set_title(&format!(
+                "Server unreachable since {} · retrying in {retry_secs}s",
+                format_clock_time(*since)
+            ));
+            banner.set_revealed(true);
+        }
+    }
+}
+
+/// Format a point in time as local HH:MM:SS
+fn format_clock_time(time: SystemTime) -> String {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0);
+    glib::DateTime::from_unix_local(secs)
+        .and_then(|dt| dt.format("%H:%M:%S"))
+        .map(|s| s.to_string())
+        .unwrap_or_default()
+}
+
 fn update_stats(state: &AppState, widgets: &Widgets) {
     widgets
         .pending_label
-        .set_text(&state.count_for("pending").to_string());
+        .set_text(&state.count_for(&DownloadStatus::Pending).to_string());
     widgets
         .downloading_label
-        .set_text(&state.count_for("downloading").to_string());
+        .set_text(&state.count_for(&DownloadStatus::Downloading).to_string());
     widgets
         .success_label
-        .set_text(&state.count_for("success").to_string());
+        .set_text(&state.count_for(&DownloadStatus::Success).to_string());
     widgets
         .error_count_label
-        .set_text(&state.count_for("error").to_string());
+        .set_text(&state.count_for(&DownloadStatus::Error).to_string());
 
     if let Some(sys) = &state.system {
         widgets.system_label.set_text(&format!(
@@ -150,354 +423,460 @@ fn update_stats(state: &AppState, widgets: &Widgets) {
     }
 }
 
-fn update_downloads_list(
-    state: &Rc<RefCell<AppState>>,
-    widgets: &Rc<Widgets>,
-    settings: &Rc<RefCell<AppSettings>>,
-) {
-    while let Some(child) = widgets.downloads_list.first_child() {
-        widgets.downloads_list.remove(&child);
+/// Rebuild the sidebar if its entries changed (collections come from the
+/// server config), keeping the selected entry where it still exists
+fn update_sidebar(state: &Rc<RefCell<AppState>>, widgets: &Widgets, views: &[SavedView]) {
+    let pages: Vec<&'static str> = PAGES.iter().map(|(name, _, _)| *name).collect();
+    let items = SidebarItem::list(&pages, state.borrow().config.as_ref(), views);
+    if *widgets.sidebar_items.borrow() != items {
+        let selected = selected_sidebar_item(widgets).map(|item| item.key());
+        widgets.sidebar_list.remove_all();
+        *widgets.sidebar_items.borrow_mut() = items.clone();
+        let counts = items
+            .iter()
+            .map(|item| {
+                let (row, count) = build_sidebar_row(item);
+                widgets.sidebar_list.append(&row);
+                count
+            })
+            .collect();
+        *widgets.sidebar_counts.borrow_mut() = counts;
+        if !selected.is_some_and(|key| select_sidebar_item(widgets, &key)) {
+            select_sidebar_item(widgets, "page:downloads");
+        }
     }
+    update_sidebar_counts(&state.borrow(), widgets);
+}
 
-    let s = state.borrow();
-    let filtered = s.filtered_downloads();
+/// Show how many downloads each sidebar entry matches
+fn update_sidebar_counts(state: &AppState, widgets: &Widgets) {
+    let items = widgets.sidebar_items.borrow();
+    for (item, label) in items.iter().zip(widgets.sidebar_counts.borrow().iter()) {
+        let count = item.query().and_then(|query| state.count_matching(&query));
+        label.set_text(&count.map(|c| c.to_string()).unwrap_or_default());
+        label.set_visible(count.is_some());
+    }
+}
 
+fn selected_sidebar_item(widgets: &Widgets) -> Option<SidebarItem> {
+    let row = widgets.sidebar_list.selected_row()?;
     widgets
-        .downloads_count_label
-        .set_text(&format!("{} shown", filtered.len()));
+        .sidebar_items
+        .borrow()
+        .get(row.index() as usize)
+        .cloned()
+}
 
-    if filtered.is_empty() {
-        let row = adw::ActionRow::builder()
-            .title("No downloads")
-            .subtitle(if s.downloads.is_empty() {
-                "Add URLs to get started"
-            } else {
-                "No downloads match the current filter"
-            })
-            .build();
-        widgets.downloads_list.append(&row);
+/// Select the sidebar entry with this key, returning whether there is one
+fn select_sidebar_item(widgets: &Widgets, key: &str) -> bool {
+    let index = widgets
+        .sidebar_items
+        .borrow()
+        .iter()
+        .position(|item| item.key() == key);
+    let row = index.and_then(|i| widgets.sidebar_list.row_at_index(i as i32));
+    widgets.sidebar_list.select_row(row.as_ref());
+    row.is_some()
+}
+
+/// Show the page of a sidebar entry, narrowing the Downloads page to its
+/// query. Moving to another query starts over from everything it matches.
+fn show_sidebar_item(item: &SidebarItem, state: &Rc<RefCell<AppState>>, widgets: &Widgets) {
+    widgets.view_stack.set_visible_child_name(item.page());
+    widgets
+        .content_page
+        .set_title(&sidebar_title_and_icon(item).0);
+    let query = match item.query() {
+        Some(query) if item.page() == "downloads" => query,
+        _ => return,
+    };
+    if state.borrow().view_query == query {
         return;
     }
+    {
+        let mut s = state.borrow_mut();
+        s.view_query = query;
+        s.status_filter = StatusFilter::All;
+        s.download_search.clear();
+    }
+    widgets.downloads_search.set_text("");
+    widgets.downloads_search_bar.set_search_mode(false);
+    if let Some((_, all)) = widgets.status_buttons.first() {
+        all.set_active(true);
+    }
+    if let SidebarItem::View(view) = item {
+        state.borrow_mut().sort_order = view.sort;
+        show_sort_order(widgets, view.sort);
+    }
+    update_downloads_list(&state.borrow(), widgets);
+}
 
-    for dl in &filtered {
-        let row = build_download_row(dl, widgets, state, settings);
-        widgets.downloads_list.append(&row);
+/// Show `order` in the sort dropdown and direction button
+fn show_sort_order(widgets: &Widgets, order: SortOrder) {
+    if let Some(index) = SortKey::ALL.iter().position(|&k| k == order.key) {
+        widgets.downloads_sort.set_selected(index as u32);
     }
+    let (icon, tooltip) = if order.descending {
+        ("view-sort-descending-symbolic", "Descending")
+    } else {
+        ("view-sort-ascending-symbolic", "Ascending")
+    };
+    widgets.downloads_sort_direction.set_icon_name(icon);
+    widgets
+        .downloads_sort_direction
+        .set_tooltip_text(Some(tooltip));
 }
 
-fn build_download_row(
-    dl: &types::Download,
-    widgets: &Rc<Widgets>,
+/// Sort the Downloads page by `order`, remembered across launches
+fn set_sort_order(
     state: &Rc<RefCell<AppState>>,
+    widgets: &Widgets,
     settings: &Rc<RefCell<AppSettings>>,
-) -> gtk::ListBoxRow {
-    let outer_box = gtk::Box::builder()
-        .orientation(gtk::Orientation::Horizontal)
-        .spacing(8)
-        .margin_top(6)
-        .margin_bottom(6)
-        .margin_start(12)
-        .margin_end(12)
-        .build();
-
-    // Info column
-    let info_box = gtk::Box::builder()
-        .orientation(gtk::Orientation::Vertical)
-        .spacing(2)
-        .hexpand(true)
-        .build();
+    order: SortOrder,
+) {
+    state.borrow_mut().sort_order = order;
+    settings.borrow_mut().sort_order = order;
+    save_settings(&settings.borrow());
+    show_sort_order(widgets, order);
+    update_downloads_list(&state.borrow(), widgets);
+}
 
-    let title_label = gtk::Label::builder()
-        .label(dl.display_title())
-        .halign(gtk::Align::Start)
-        .ellipsize(gtk::pango::EllipsizeMode::End)
-        .css_classes(["heading"])
-        .build();
+fn sidebar_title_and_icon(item: &SidebarItem) -> (String, &'static str) {
+    match item {
+        SidebarItem::Page(page) => PAGES
+            .iter()
+            .find(|(name, _, _)| name == page)
+            .map(|(_, title, icon)| (title.to_string(), *icon))
+            .unwrap_or_default(),
+        SidebarItem::Collection(name) => (name.clone(), "folder-symbolic"),
+        SidebarItem::View(view) => (view.name.clone(), "folder-saved-search-symbolic"),
+    }
+}
 
-    let url_label = gtk::Label::builder()
-        .label(&dl.url)
-        .halign(gtk::Align::Start)
-        .ellipsize(gtk::pango::EllipsizeMode::End)
-        .css_classes(["dim-label", "caption"])
-        .build();
+fn update_downloads_list(state: &AppState, widgets: &Widgets) {
+    let filtered = state.filtered_downloads();
+
+    let query = Filter::parse(&state.download_search);
+    let terms = query
+        .as_ref()
+        .map(Filter::highlight_terms)
+        .unwrap_or_default();
+    match &query {
+        Ok(_) => {
+            widgets.downloads_search.remove_css_class("error");
+            widgets.downloads_search_error.set_visible(false);
+        }
+        Err(e) => {
+            widgets.downloads_search.add_css_class("error");
+            widgets.downloads_search_error.set_text(&e.to_string());
+            widgets.downloads_search_error.set_visible(true);
+        }
+    }
 
-    let meta_label = gtk::Label::builder()
-        .label(format!("{} · ID {}", dl.collection, dl.id))
-        .halign(gtk::Align::Start)
-        .css_classes(["dim-label", "caption"])
-        .build();
+    widgets
+        .downloads_count_label
+        .set_text(&if state.download_search.trim().is_empty() {
+            format!("{} shown", filtered.len())
+        } else {
+            format!("{} of {} shown", filtered.len(), state.downloads.len())
+        });
 
-    info_box.append(&title_label);
-    info_box.append(&url_label);
-    info_box.append(&meta_label);
+    if filtered.is_empty() {
+        widgets.downloads_empty.set_description(Some(
+            if matches!(state.connection, ConnectionStatus::Unreachable { .. }) {
+                "Unable to reach the DLM server"
+            } else if state.downloads.is_empty() {
+                "Add URLs to get started"
+            } else if !state.download_search.trim().is_empty() {
+                "No downloads match the search"
+            } else {
+                "No downloads match the current filter"
+            },
+        ));
+        widgets.downloads_stack.set_visible_child_name("empty");
+    } else {
+        widgets.downloads_stack.set_visible_child_name("list");
+    }
 
-    // Status badge
-    let status_css = match dl.status.as_str() {
-        "pending" => "accent",
-        "downloading" => "warning",
-        "success" => "success",
-        "error" => "error",
-        _ => "dim-label",
+    // Keep the selection on the same downloads, since replaced items lose it
+    let selected: HashSet<i64> = selected_downloads(widgets).iter().map(|d| d.id).collect();
+    download_object::sync_entries(&widgets.downloads_store, &download_entries(state));
+    // Unchanged items aren't rebound, so rebind all when the highlight
+    // changes or relative times move on a minute
+    let context = RowContext {
+        highlight: terms,
+        now: Timestamp::now().to_minute(),
     };
-    let status_label = gtk::Label::builder()
-        .label(&dl.status)
-        .css_classes([status_css, "status-badge"])
-        .valign(gtk::Align::Center)
-        .build();
-
-    // Action buttons
-    let actions_box = gtk::Box::builder()
-        .orientation(gtk::Orientation::Horizontal)
-        .spacing(2)
-        .valign(gtk::Align::Center)
-        .build();
-
-    // Open folder button (always available if we have config)
-    let collection = dl.collection.clone();
-    let open_btn = gtk::Button::builder()
-        .icon_name("folder-open-symbolic")
-        .tooltip_text("Open download folder")
-        .css_classes(["flat", "circular"])
-        .build();
-    open_btn.connect_clicked(clone!(
-        #[strong]
-        state,
-        #[strong]
-        widgets,
-        move |_| {
-            let s = state.borrow();
-            if let Some(dir) = s.dir_for_collection(&collection) {
-                let dir = dir.to_string();
-                drop(s);
-                if let Err(e) = open::that(&dir) {
-                    widgets.show_toast(&format!("Failed to open folder: {e}"));
-                }
-            } else {
-                drop(s);
-                widgets.show_toast("Collection directory not found in config");
+    if *widgets.downloads_context.borrow() != context {
+        widgets.downloads_context.replace(context);
+        let n = widgets.downloads_store.n_items();
+        widgets.downloads_store.items_changed(0, n, n);
+    }
+    if !selected.is_empty() {
+        let store = &widgets.downloads_store;
+        let bits = gtk::Bitset::new_empty();
+        for i in 0..store.n_items() {
+            if let Some(obj) = store.item(i).and_downcast::<DownloadObject>()
+                && selected.contains(&obj.id())
+            {
+                bits.add(i);
             }
         }
-    ));
-    actions_box.append(&open_btn);
-
-    // Status-specific action
-    let dl_id = dl.id;
-    match dl.status.as_str() {
-        "success" => {
-            let btn = gtk::Button::builder()
-                .icon_name("view-refresh-symbolic")
-                .tooltip_text("Redownload")
-                .css_classes(["flat", "circular"])
-                .build();
-            btn.connect_clicked(clone!(
-                #[strong]
-                widgets,
-                #[strong]
-                state,
-                #[strong]
-                settings,
-                move |_| {
-                    let api_url = settings.borrow().api_url.clone();
-                    do_action(
-                        &api_url,
-                        move |url| api::redownload(&url, dl_id),
-                        &widgets,
-                        &state,
-                        &settings,
-                    );
-                }
-            ));
-            actions_box.append(&btn);
-        }
-        "error" => {
-            let btn = gtk::Button::builder()
-                .icon_name("view-refresh-symbolic")
-                .tooltip_text("Retry")
-                .css_classes(["flat", "circular"])
-                .build();
-            btn.connect_clicked(clone!(
-                #[strong]
-                widgets,
-                #[strong]
-                state,
-                #[strong]
-                settings,
-                move |_| {
-                    let api_url = settings.borrow().api_url.clone();
-                    do_action(
-                        &api_url,
-                        move |url| api::retry_download(&url, dl_id),
-                        &widgets,
-                        &state,
-                        &settings,
-                    );
-                }
-            ));
-            actions_box.append(&btn);
-        }
-        "pending" => {
-            let btn = gtk::Button::builder()
-                .icon_name("user-trash-symbolic")
-                .tooltip_text("Delete")
-                .css_classes(["flat", "circular"])
-                .build();
-            btn.connect_clicked(clone!(
-                #[strong]
-                widgets,
-                #[strong]
-                state,
-                #[strong]
-                settings,
-                move |_| {
-                    let api_url = settings.borrow().api_url.clone();
-                    do_action(
-                        &api_url,
-                        move |url| api::delete_download(&url, dl_id),
-                        &widgets,
-                        &state,
-                        &settings,
-                    );
-                }
-            ));
-            actions_box.append(&btn);
+        let all = gtk::Bitset::new_range(0, store.n_items());
+        widgets.downloads_selection.set_selection(&bits, &all);
+    }
+    update_selection_bar(state, widgets);
+}
+
+/// Items of the Downloads list: the filtered downloads, under a header per
+/// group when grouping, leaving out the downloads of collapsed groups
+fn download_entries(state: &AppState) -> Vec<ListEntry<'_>> {
+    let mut entries = Vec::new();
+    for group in state.grouped_downloads() {
+        if state.group_by == GroupBy::None {
+            entries.extend(group.downloads.into_iter().map(ListEntry::Download));
+            continue;
         }
-        "downloading" => {
-            let btn = gtk::Button::builder()
-                .icon_name("media-playback-stop-symbolic")
-                .tooltip_text("Reset to pending")
-                .css_classes(["flat", "circular"])
-                .build();
-            btn.connect_clicked(clone!(
-                #[strong]
-                widgets,
-                #[strong]
-                state,
-                #[strong]
-                settings,
-                move |_| {
-                    let api_url = settings.borrow().api_url.clone();
-                    do_action(
-                        &api_url,
-                        move |url| api::reset_download(&url, dl_id),
-                        &widgets,
-                        &state,
-                        &settings,
-                    );
-                }
-            ));
-            actions_box.append(&btn);
+        let collapsed = state.collapsed_groups.contains(&group.key);
+        entries.push(ListEntry::Group(group.header(collapsed)));
+        if !collapsed {
+            entries.extend(group.downloads.into_iter().map(ListEntry::Download));
         }
-        _ => {}
     }
+    entries
+}
 
-    outer_box.append(&info_box);
-    outer_box.append(&status_label);
-    outer_box.append(&actions_box);
-
-    gtk::ListBoxRow::builder()
-        .child(&outer_box)
-        .activatable(false)
-        .build()
+/// Downloads currently selected in the Downloads list, in list order
+fn selected_downloads(widgets: &Widgets) -> Vec<Download> {
+    let model = &widgets.downloads_selection;
+    let bits = model.selection();
+    (0..bits.size() as u32)
+        .filter_map(|n| model.item(bits.nth(n)).and_downcast::<DownloadObject>())
+        .map(|obj| obj.download().clone())
+        .collect()
 }
 
-fn update_errors_list(
-    state: &Rc<RefCell<AppState>>,
-    widgets: &Rc<Widgets>,
-    settings: &Rc<RefCell<AppSettings>>,
-) {
-    while let Some(child) = widgets.errors_list.first_child() {
-        widgets.errors_list.remove(&child);
+/// Show the selection bar with per-action counts while anything is selected
+fn update_selection_bar(state: &AppState, widgets: &Widgets) {
+    let selected = selected_downloads(widgets);
+    // Also shown for the progress of a group's bulk action
+    widgets
+        .selection_bar
+        .set_revealed(!selected.is_empty() || state.bulk_running);
+    widgets.selection_label.set_text(&if selected.is_empty() {
+        "Updating group…".to_string()
+    } else {
+        format!("{} selected", selected.len())
+    });
+
+    for (action, button) in &widgets.selection_buttons {
+        let count = selected
+            .iter()
+            .filter(|d| action.applies_to(&d.status))
+            .count();
+        button.set_label(&format!("{} ({count})", action.label()));
+        button.set_sensitive(count > 0 && !state.bulk_running);
     }
+}
 
-    let s = state.borrow();
-    let errors = s.error_downloads();
+fn update_errors_list(state: &AppState, widgets: &Widgets) {
+    let errors = state.error_downloads();
 
     if errors.is_empty() {
         widgets.errors_stack.set_visible_child_name("empty");
-        return;
+    } else {
+        widgets.errors_stack.set_visible_child_name("list");
     }
 
-    widgets.errors_stack.set_visible_child_name("list");
-
-    for dl in &errors {
-        let row = adw::ActionRow::builder()
-            .title(format!("ID {}: {}", dl.id, dl.display_title()))
-            .subtitle(&dl.url)
-            .build();
+    download_object::sync_store(&widgets.errors_store, &errors);
+    let context = RowContext {
+        highlight: Vec::new(),
+        now: Timestamp::now().to_minute(),
+    };
+    if *widgets.errors_context.borrow() != context {
+        widgets.errors_context.replace(context);
+        let n = widgets.errors_store.n_items();
+        widgets.errors_store.items_changed(0, n, n);
+    }
+}
 
-        if let Some(err) = &dl.error_message {
-            let err_label = gtk::Label::builder()
-                .label(err)
-                .css_classes(["error", "caption"])
-                .wrap(true)
-                .halign(gtk::Align::Start)
-                .max_width_chars(60)
-                .build();
-            row.add_suffix(&err_label);
+/// Factory creating [`DownloadRow`]s for [`DownloadObject`]s and
+/// [`GroupRow`]s for [`GroupObject`]s, bound with the shared `context`
+fn build_download_factory(
+    errors_style: bool,
+    context: Rc<RefCell<RowContext>>,
+) -> gtk::SignalListItemFactory {
+    let factory = gtk::SignalListItemFactory::new();
+    factory.connect_setup(move |_, item| {
+        let Some(item) = item.downcast_ref::<gtk::ListItem>() else {
+            return;
+        };
+        item.set_child(Some(&DownloadRow::new(errors_style)));
+    });
+    // Group headers share the list, so a recycled item may need the other
+    // kind of row
+    factory.connect_bind(move |_, item| {
+        let Some(item) = item.downcast_ref::<gtk::ListItem>() else {
+            return;
+        };
+        if let Some(obj) = item.item().and_downcast::<DownloadObject>() {
+            let row = item
+                .child()
+                .and_downcast::<DownloadRow>()
+                .unwrap_or_else(|| {
+                    let row = DownloadRow::new(errors_style);
+                    item.set_child(Some(&row));
+                    row
+                });
+            item.set_selectable(true);
+            row.bind(&obj.download(), &context.borrow());
+        } else if let Some(obj) = item.item().and_downcast::<GroupObject>() {
+            let row = item.child().and_downcast::<GroupRow>().unwrap_or_else(|| {
+                let row = GroupRow::default();
+                item.set_child(Some(&row));
+                row
+            });
+            item.set_selectable(false);
+            row.bind(&obj.header());
         }
+    });
+    factory
+}
 
-        let dl_id = dl.id;
-        let retry_btn = gtk::Button::builder()
-            .icon_name("view-refresh-symbolic")
-            .tooltip_text("Retry")
-            .css_classes(["flat", "circular"])
-            .valign(gtk::Align::Center)
-            .build();
-        retry_btn.connect_clicked(clone!(
-            #[strong]
-            widgets,
-            #[strong]
-            state,
-            #[strong]
-            settings,
-            move |_| {
-                let api_url = settings.borrow().api_url.clone();
-                do_action(
-                    &api_url,
-                    move |url| api::retry_download(&url, dl_id),
-                    &widgets,
-                    &state,
-                    &settings,
-                );
-            }
-        ));
-        row.add_suffix(&retry_btn);
+/// Text tag marking a download id in the log, clickable
+const LOG_ID_TAG: &str = "download-id";
+/// Text tag marking a URL in the log, clickable
+const LOG_URL_TAG: &str = "url";
+
+/// Tags for the log buffer: line colors per level, dimmed timestamps, and
+/// ids and URLs styled as links
+fn create_log_tags(buffer: &gtk::TextBuffer) {
+    let tags = [
+        gtk::TextTag::builder()
+            .name("error")
+            .foreground("#e01b24")
+            .build(),
+        gtk::TextTag::builder()
+            .name("debug")
+            .foreground("#77767b")
+            .build(),
+        gtk::TextTag::builder()
+            .name("timestamp")
+            .foreground("#77767b")
+            .build(),
+        gtk::TextTag::builder()
+            .name(LOG_ID_TAG)
+            .foreground("#3584e4")
+            .underline(gtk::pango::Underline::Single)
+            .build(),
+        gtk::TextTag::builder()
+            .name(LOG_URL_TAG)
+            .foreground("#3584e4")
+            .build(),
+    ];
+    for tag in tags {
+        buffer.tag_table().add(&tag);
+    }
+}
 
-        let dl_id = dl.id;
-        let delete_btn = gtk::Button::builder()
-            .icon_name("user-trash-symbolic")
-            .tooltip_text("Delete")
-            .css_classes(["flat", "circular"])
-            .valign(gtk::Align::Center)
-            .build();
-        delete_btn.connect_clicked(clone!(
-            #[strong]
-            widgets,
-            #[strong]
-            state,
-            #[strong]
-            settings,
-            move |_| {
-                let api_url = settings.borrow().api_url.clone();
-                do_action(
-                    &api_url,
-                    move |url| api::delete_download(&url, dl_id),
-                    &widgets,
-                    &state,
-                    &settings,
-                );
-            }
-        ));
-        row.add_suffix(&delete_btn);
+fn update_logs_view(state: &AppState, widgets: &Widgets) {
+    let entries = state.filtered_logs();
+    let buffer = &widgets.logs_buffer;
+    let text: Vec<&str> = entries.iter().map(|e| e.text.as_str()).collect();
+    buffer.set_text(&text.join("\n"));
+
+    // Ranges are bytes within a line; the buffer counts characters
+    let mut line_start = 0;
+    for entry in entries {
+        let offset = |byte: usize| line_start + entry.text[..byte].chars().count() as i32;
+        let tag = |name: &str, range: std::ops::Range<usize>| {
+            buffer.apply_tag_by_name(
+                name,
+                &buffer.iter_at_offset(offset(range.start)),
+                &buffer.iter_at_offset(offset(range.end)),
+            );
+        };
+        if let Some(name) = entry.level.tag_name() {
+            tag(name, 0..entry.text.len());
+        }
+        if let Some(range) = &entry.time_range {
+            tag("timestamp", range.clone());
+        }
+        for range in &entry.urls {
+            tag(LOG_URL_TAG, range.clone());
+        }
+        for (_, range) in &entry.ids {
+            tag(LOG_ID_TAG, range.clone());
+        }
+        line_start = offset(entry.text.len()) + 1;
+    }
+}
 
-        widgets.errors_list.append(&row);
+/// Text of the run of `tag` around `iter`, if it has the tag
+fn tagged_text(buffer: &gtk::TextBuffer, iter: &gtk::TextIter, tag: &str) -> Option<String> {
+    let tag = buffer.tag_table().lookup(tag)?;
+    if !iter.has_tag(&tag) {
+        return None;
+    }
+    let (mut start, mut end) = (*iter, *iter);
+    if !start.starts_tag(Some(&tag)) {
+        start.backward_to_tag_toggle(Some(&tag));
     }
+    end.forward_to_tag_toggle(Some(&tag));
+    Some(buffer.text(&start, &end, false).to_string())
 }
 
-fn update_logs_view(state: &AppState, widgets: &Widgets) {
-    let lines = state.filtered_logs();
-    let text = lines.join("\n");
-    widgets.logs_buffer.set_text(&text);
+/// Show download `id` on the Downloads page, selected and scrolled to,
+/// clearing whatever hides it: a sidebar entry, the status filter, the
+/// search, or a collapsed group
+fn reveal_download(state: &Rc<RefCell<AppState>>, widgets: &Widgets, id: i64) {
+    let Some(dl) = state
+        .borrow()
+        .downloads
+        .iter()
+        .find(|d| d.id == id)
+        .cloned()
+    else {
+        widgets.show_toast(&format!("Download {id} is no longer on the server"));
+        return;
+    };
+    select_sidebar_item(widgets, "page:downloads");
+    widgets.split_view.set_show_content(true);
+
+    let hidden = !state
+        .borrow()
+        .filtered_downloads()
+        .iter()
+        .any(|d| d.id == id);
+    if hidden {
+        state.borrow_mut().download_search = format!("id:{id}");
+        widgets.downloads_search.set_text(&format!("id:{id}"));
+        widgets.downloads_search_bar.set_search_mode(true);
+        if let Some((_, all)) = widgets.status_buttons.first() {
+            all.set_active(true);
+        }
+    }
+    {
+        let mut s = state.borrow_mut();
+        let key = s.group_by.key(&dl);
+        s.collapsed_groups.remove(&key);
+    }
+    update_downloads_list(&state.borrow(), widgets);
+
+    let store = &widgets.downloads_store;
+    let position = (0..store.n_items()).find(|&i| {
+        store
+            .item(i)
+            .and_downcast::<DownloadObject>()
+            .is_some_and(|obj| obj.id() == id)
+    });
+    if let Some(position) = position {
+        widgets.downloads_list.scroll_to(
+            position,
+            gtk::ListScrollFlags::FOCUS | gtk::ListScrollFlags::SELECT,
+            None,
+        );
+    }
 }
 
 fn update_config_view(state: &AppState, widgets: &Widgets) {
@@ -547,16 +926,18 @@ fn update_config_view(state: &AppState, widgets: &Widgets) {
 // Dialogs
 // ============================================================================
 
+/// Open the add dialog, optionally prefilled (e.g. with dropped links)
 fn show_add_urls_dialog(
     window: &adw::ApplicationWindow,
     widgets: &Rc<Widgets>,
     state: &Rc<RefCell<AppState>>,
     settings: &Rc<RefCell<AppSettings>>,
+    prefill: &[String],
 ) {
     let dialog = adw::Dialog::builder()
         .title("Add URLs")
-        .content_width(500)
-        .content_height(350)
+        .content_width(560)
+        .content_height(520)
         .build();
 
     let toolbar_view = adw::ToolbarView::new();
@@ -588,12 +969,76 @@ fn show_add_urls_dialog(
         .right_margin(8)
         .build();
 
+    text_view.buffer().set_text(&prefill.join("\n"));
+
+    // Pasted chat messages, markdown or web pages become their links
+    text_view.connect_paste_clipboard(clone!(
+        #[strong]
+        hint,
+        move |text_view| {
+            text_view.stop_signal_emission_by_name("paste-clipboard");
+            let clipboard = text_view.clipboard();
+            let text_view = text_view.clone();
+            let hint = hint.clone();
+            glib::spawn_future_local(async move {
+                let (text, urls) = pasted_urls(&clipboard).await;
+                let buffer = text_view.buffer();
+                buffer.delete_selection(true, true);
+                if urls.is_empty() {
+                    // Nothing link-like: paste as usual
+                    if let Some(text) = text {
+                        buffer.insert_at_cursor(&text);
+                    }
+                    return;
+                }
+
+                let mut insert = urls.join("\n");
+                let cursor = buffer.iter_at_mark(&buffer.get_insert());
+                if !cursor.starts_line() {
+                    insert.insert(0, '\n');
+                }
+                if !cursor.ends_line() {
+                    insert.push('\n');
+                }
+                buffer.insert_at_cursor(&insert);
+                hint.set_label(&match urls.len() {
+                    1 => "Found 1 link in the pasted text, review it below".to_string(),
+                    n => format!("Found {n} links in the pasted text, review them below"),
+                });
+            });
+        }
+    ));
+    let invalid_tag = gtk::TextTag::builder()
+        .name("invalid")
+        .underline(gtk::pango::Underline::Error)
+        .build();
+    text_view.buffer().tag_table().add(&invalid_tag);
+
     let scroll = gtk::ScrolledWindow::builder()
         .child(&text_view)
         .vexpand(true)
         .build();
     content.append(&scroll);
 
+    // Where each URL would go, and which are already queued
+    let preview = gtk::ListBox::builder()
+        .selection_mode(gtk::SelectionMode::None)
+        .css_classes(["boxed-list"])
+        .build();
+    let preview_scroll = gtk::ScrolledWindow::builder()
+        .hscrollbar_policy(gtk::PolicyType::Never)
+        .propagate_natural_height(true)
+        .max_content_height(200)
+        .child(&preview)
+        .build();
+    content.append(&preview_scroll);
+
+    let summary = gtk::Label::builder()
+        .css_classes(["dim-label", "caption"])
+        .halign(gtk::Align::Start)
+        .build();
+    content.append(&summary);
+
     let submit_btn = gtk::Button::builder()
         .label("Add Downloads")
         .css_classes(["suggested-action", "pill"])
@@ -601,6 +1046,57 @@ fn show_add_urls_dialog(
         .build();
     content.append(&submit_btn);
 
+    // Re-check every entry as the text changes
+    let refresh_preview = clone!(
+        #[strong]
+        state,
+        #[strong]
+        preview,
+        #[strong]
+        preview_scroll,
+        #[strong]
+        summary,
+        #[strong]
+        submit_btn,
+        #[strong]
+        invalid_tag,
+        move |buffer: &gtk::TextBuffer| {
+            let text = buffer.text(&buffer.start_iter(), &buffer.end_iter(), false);
+            let entries = url_check::check_text(&state.borrow(), &text);
+
+            buffer.remove_tag(&invalid_tag, &buffer.start_iter(), &buffer.end_iter());
+            for entry in entries
+                .iter()
+                .filter(|e| e.verdict == url_check::Verdict::Invalid)
+            {
+                let line = entry.line as i32;
+                if let (Some(start), Some(end)) = (
+                    buffer.iter_at_line_offset(line, entry.chars.start as i32),
+                    buffer.iter_at_line_offset(line, entry.chars.end as i32),
+                ) {
+                    buffer.apply_tag(&invalid_tag, &start, &end);
+                }
+            }
+
+            preview.remove_all();
+            for entry in &entries {
+                preview.append(&build_url_preview_row(entry));
+            }
+            preview_scroll.set_visible(!entries.is_empty());
+            summary.set_visible(!entries.is_empty());
+            summary.set_label(&url_check::summary(&entries));
+
+            let count = entries.iter().filter(|e| e.is_accepted()).count();
+            submit_btn.set_sensitive(count > 0);
+            submit_btn.set_label(&match count {
+                0 | 1 => "Add Download".to_string(),
+                n => format!("Add {n} Downloads"),
+            });
+        }
+    );
+    refresh_preview(&text_view.buffer());
+    text_view.buffer().connect_changed(refresh_preview);
+
     toolbar_view.set_content(Some(&content));
     dialog.set_child(Some(&toolbar_view));
 
@@ -620,42 +1116,928 @@ fn show_add_urls_dialog(
             let text = buffer
                 .text(&buffer.start_iter(), &buffer.end_iter(), false)
                 .to_string();
-            let urls: Vec<String> = text
-                .split('\n')
-                .flat_map(|l| l.split(','))
-                .map(|s| s.trim().to_string())
-                .filter(|s| !s.is_empty())
-                .collect();
+            let urls = url_check::accepted(&url_check::check_text(&state.borrow(), &text));
 
             if urls.is_empty() {
-                widgets.show_toast("No URLs entered");
+                widgets.show_toast("No URLs to add");
                 return;
             }
 
-            let count = urls.len();
-            let api_url = settings.borrow().api_url.clone();
-            let widgets2 = widgets.clone();
-            let state2 = state.clone();
-            let settings2 = settings.clone();
+            submit_urls(urls, &widgets, &state, &settings);
+            dialog.close();
+        }
+    ));
+
+    dialog.present(Some(window));
+}
+
+/// Largest copied HTML read for links
+const MAX_PASTED_HTML_BYTES: usize = 1024 * 1024;
+
+/// Clipboard text and the links in it, preferring the link targets of copied
+/// HTML over the URLs visible in its text
+async fn pasted_urls(clipboard: &gtk::gdk::Clipboard) -> (Option<String>, Vec<String>) {
+    let text = clipboard
+        .read_text_future()
+        .await
+        .ok()
+        .flatten()
+        .map(|t| t.to_string());
+    if clipboard.formats().contain_mime_type("text/html")
+        && let Some(html) = read_clipboard_html(clipboard).await
+    {
+        let urls = links::urls_from_html(&html);
+        if !urls.is_empty() {
+            return (text, urls);
+        }
+    }
+    let urls = text.as_deref().map(links::extract_urls).unwrap_or_default();
+    (text, urls)
+}
+
+async fn read_clipboard_html(clipboard: &gtk::gdk::Clipboard) -> Option<String> {
+    let (stream, _) = clipboard
+        .read_future(&["text/html"], glib::Priority::DEFAULT)
+        .await
+        .ok()?;
+    let mut data = Vec::new();
+    loop {
+        let bytes = stream
+            .read_bytes_future(64 * 1024, glib::Priority::DEFAULT)
+            .await
+            .ok()?;
+        if bytes.is_empty() {
+            break;
+        }
+        data.extend_from_slice(&bytes);
+        if data.len() > MAX_PASTED_HTML_BYTES {
+            return None;
+        }
+    }
+    Some(links::html_from_bytes(&data))
+}
+
+/// Row describing what adding an entry would do: the collection it routes
+/// to, or why it will be skipped
+fn build_url_preview_row(entry: &url_check::Entry) -> adw::ActionRow {
+    use url_check::Verdict;
+
+    let (subtitle, icon, css) = match &entry.verdict {
+        Verdict::Accepted(Some(collection)) => (
+            format!("→ {collection}"),
+            "object-select-symbolic",
+            "success",
+        ),
+        Verdict::Accepted(None) => (
+            "Collection unknown until the config loads".to_string(),
+            "object-select-symbolic",
+            "dim-label",
+        ),
+        Verdict::Invalid => (
+            "Not an http(s) URL, will be skipped".to_string(),
+            "dialog-error-symbolic",
+            "error",
+        ),
+        Verdict::AlreadyAdded(status) => (
+            format!("Already added · {status}, will be skipped"),
+            "dialog-warning-symbolic",
+            "warning",
+        ),
+        Verdict::Repeated => (
+            "Listed more than once, added once".to_string(),
+            "dialog-information-symbolic",
+            "dim-label",
+        ),
+        Verdict::Unmatched => (
+            "No collection matches this domain, will be skipped".to_string(),
+            "dialog-warning-symbolic",
+            "warning",
+        ),
+    };
+    let row = adw::ActionRow::builder()
+        .title(&entry.url)
+        .subtitle(subtitle)
+        .use_markup(false)
+        .title_lines(1)
+        .build();
+    row.add_prefix(
+        &gtk::Image::builder()
+            .icon_name(icon)
+            .css_classes([css])
+            .build(),
+    );
+    row
+}
+
+/// Ask before queueing URLs that came from outside the app (e.g. a `dlm://`
+/// link), listing the collection each would be filed under
+fn show_add_confirmation(
+    window: &adw::ApplicationWindow,
+    urls: Vec<String>,
+    widgets: &Rc<Widgets>,
+    state: &Rc<RefCell<AppState>>,
+    settings: &Rc<RefCell<AppSettings>>,
+) {
+    let heading = match urls.len() {
+        1 => "Add URL?".to_string(),
+        n => format!("Add {n} URLs?"),
+    };
+    let dialog = adw::AlertDialog::new(Some(&heading), Some("Opened from a dlm:// link"));
+
+    let list = gtk::ListBox::builder()
+        .selection_mode(gtk::SelectionMode::None)
+        .css_classes(["boxed-list"])
+        .build();
+    let entries = url_check::check_urls(&state.borrow(), &urls);
+    for entry in &entries {
+        list.append(&build_url_preview_row(entry));
+    }
+    let valid = url_check::accepted(&entries);
+    dialog.set_body(&format!(
+        "Opened from a dlm:// link\n{}",
+        url_check::summary(&entries)
+    ));
+
+    let scroll = gtk::ScrolledWindow::builder()
+        .hscrollbar_policy(gtk::PolicyType::Never)
+        .propagate_natural_height(true)
+        .max_content_height(320)
+        .child(&list)
+        .build();
+    dialog.set_extra_child(Some(&scroll));
+
+    dialog.add_responses(&[("cancel", "_Cancel"), ("add", "_Add")]);
+    dialog.set_response_appearance("add", adw::ResponseAppearance::Suggested);
+    dialog.set_response_enabled("add", !valid.is_empty());
+    dialog.set_default_response(Some("add"));
+    dialog.set_close_response("cancel");
+
+    dialog.connect_response(
+        Some("add"),
+        clone!(
+            #[strong]
+            widgets,
+            #[strong]
+            state,
+            #[strong]
+            settings,
+            move |_, _| {
+                submit_urls(valid.clone(), &widgets, &state, &settings);
+            }
+        ),
+    );
+
+    dialog.present(Some(window));
+}
+
+/// URLs posted per request when importing
+const IMPORT_CHUNK_SIZE: usize = 50;
+/// Import preview rows shown before the rest are summarized
+const MAX_IMPORT_PREVIEW_ROWS: usize = 200;
+
+/// Pick a text, CSV, bookmark or OPML file and open its import preview
+fn choose_import_file(
+    window: &adw::ApplicationWindow,
+    widgets: &Rc<Widgets>,
+    state: &Rc<RefCell<AppState>>,
+    settings: &Rc<RefCell<AppSettings>>,
+) {
+    let supported = gtk::FileFilter::new();
+    supported.set_name(Some("URL lists, CSV, bookmarks and OPML"));
+    for suffix in [
+        "txt", "csv", "tsv", "html", "htm", "opml", "xml", "url", "desktop",
+    ] {
+        supported.add_suffix(suffix);
+    }
+    let all = gtk::FileFilter::new();
+    all.set_name(Some("All files"));
+    all.add_pattern("*");
+    let filters = gio::ListStore::new::<gtk::FileFilter>();
+    filters.append(&supported);
+    filters.append(&all);
+
+    let dialog = gtk::FileDialog::builder()
+        .title("Import URLs")
+        .accept_label("_Import")
+        .filters(&filters)
+        .default_filter(&supported)
+        .build();
+
+    let window = window.clone();
+    let widgets = widgets.clone();
+    let state = state.clone();
+    let settings = settings.clone();
+    glib::spawn_future_local(async move {
+        let Ok(file) = dialog.open_future(Some(&window)).await else {
+            return; // Cancelled
+        };
+        let Some(path) = file.path() else {
+            widgets.show_toast("Only local files can be imported");
+            return;
+        };
+        let name = file
+            .basename()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        // Large bookmark exports take a while to parse, so off the main thread
+        let source = gio::spawn_blocking(move || {
+            let meta = std::fs::metadata(&path)?;
+            if meta.len() > import::MAX_FILE_BYTES {
+                return Err(std::io::Error::other("file is too large to import"));
+            }
+            let bytes = std::fs::read(&path)?;
+            Ok(import::Source::parse(
+                &path,
+                &String::from_utf8_lossy(&bytes),
+            ))
+        })
+        .await
+        .unwrap_or_else(|_| Err(std::io::Error::other("worker thread panicked")));
+        match source {
+            Ok(source) => {
+                show_import_dialog(
+                    &window,
+                    &format!("Import {name}"),
+                    None,
+                    source,
+                    &widgets,
+                    &state,
+                    &settings,
+                );
+            }
+            Err(e) => widgets.show_toast(&format!("Could not read {name}: {e}")),
+        }
+    });
+}
+
+/// Preview URLs from an import file or backup with their routing, then add
+/// the accepted ones in chunks with progress
+fn show_import_dialog(
+    window: &adw::ApplicationWindow,
+    title: &str,
+    note: Option<&str>,
+    source: import::Source,
+    widgets: &Rc<Widgets>,
+    state: &Rc<RefCell<AppState>>,
+    settings: &Rc<RefCell<AppSettings>>,
+) {
+    let dialog = adw::Dialog::builder()
+        .title(title)
+        .content_width(600)
+        .content_height(560)
+        .build();
+
+    let toolbar_view = adw::ToolbarView::new();
+    toolbar_view.add_top_bar(&adw::HeaderBar::new());
+
+    let content = gtk::Box::builder()
+        .orientation(gtk::Orientation::Vertical)
+        .spacing(12)
+        .margin_top(12)
+        .margin_bottom(12)
+        .margin_start(12)
+        .margin_end(12)
+        .build();
+
+    if let Some(note) = note {
+        let label = gtk::Label::builder()
+            .label(note)
+            .wrap(true)
+            .xalign(0.0)
+            .build();
+        content.append(&label);
+    }
+
+    // Column for CSV, folder for bookmarks and OPML
+    let choices = source.choices();
+    let choice_row = adw::ComboRow::builder()
+        .title(match source {
+            import::Source::Table { .. } => "Column",
+            _ => "Folder",
+        })
+        .model(&gtk::StringList::new(
+            &choices.iter().map(String::as_str).collect::<Vec<_>>(),
+        ))
+        .selected(source.default_choice() as u32)
+        .build();
+    let choice_list = gtk::ListBox::builder()
+        .selection_mode(gtk::SelectionMode::None)
+        .css_classes(["boxed-list"])
+        .visible(choices.len() > 1)
+        .build();
+    choice_list.append(&choice_row);
+    content.append(&choice_list);
+
+    let preview = gtk::ListBox::builder()
+        .selection_mode(gtk::SelectionMode::None)
+        .css_classes(["boxed-list"])
+        .build();
+    let preview_scroll = gtk::ScrolledWindow::builder()
+        .hscrollbar_policy(gtk::PolicyType::Never)
+        .vexpand(true)
+        .child(&preview)
+        .build();
+    content.append(&preview_scroll);
+
+    let summary = gtk::Label::builder()
+        .css_classes(["dim-label", "caption"])
+        .halign(gtk::Align::Start)
+        .wrap(true)
+        .build();
+    content.append(&summary);
+
+    let progress = gtk::ProgressBar::builder()
+        .show_text(true)
+        .visible(false)
+        .build();
+    content.append(&progress);
+
+    let import_btn = gtk::Button::builder()
+        .css_classes(["suggested-action", "pill"])
+        .halign(gtk::Align::Center)
+        .build();
+    content.append(&import_btn);
+
+    toolbar_view.set_content(Some(&content));
+    dialog.set_child(Some(&toolbar_view));
+
+    // URLs still to import: the accepted ones, or what's left after a failure
+    let pending: Rc<RefCell<Vec<String>>> = Rc::default();
+
+    let source = Rc::new(source);
+    let refresh_preview = clone!(
+        #[strong]
+        state,
+        #[strong]
+        source,
+        #[strong]
+        preview,
+        #[strong]
+        summary,
+        #[strong]
+        import_btn,
+        #[strong]
+        pending,
+        move |choice: u32| {
+            let urls = source.urls(choice as usize);
+            let entries = url_check::check_urls(&state.borrow(), &urls);
+            preview.remove_all();
+            for entry in entries.iter().take(MAX_IMPORT_PREVIEW_ROWS) {
+                preview.append(&build_url_preview_row(entry));
+            }
+            if entries.len() > MAX_IMPORT_PREVIEW_ROWS {
+                let more = adw::ActionRow::builder()
+                    .title(format!(
+                        "and {} more",
+                        entries.len() - MAX_IMPORT_PREVIEW_ROWS
+                    ))
+                    .css_classes(["dim-label"])
+                    .build();
+                preview.append(&more);
+            }
+            summary.set_label(&if entries.is_empty() {
+                "No URLs found".to_string()
+            } else {
+                url_check::summary(&entries)
+            });
+
+            let accepted = url_check::accepted(&entries);
+            import_btn.set_sensitive(!accepted.is_empty());
+            import_btn.set_label(&match accepted.len() {
+                0 | 1 => "Add URL".to_string(),
+                n => format!("Add {n} URLs"),
+            });
+            *pending.borrow_mut() = accepted;
+        }
+    );
+    refresh_preview(choice_row.selected());
+    choice_row.connect_selected_notify(move |row| refresh_preview(row.selected()));
+
+    import_btn.connect_clicked(clone!(
+        #[strong]
+        widgets,
+        #[strong]
+        state,
+        #[strong]
+        settings,
+        #[strong]
+        pending,
+        #[weak]
+        dialog,
+        #[weak]
+        choice_list,
+        #[weak]
+        summary,
+        #[weak]
+        progress,
+        move |button| {
+            let urls = pending.borrow().clone();
+            let total = urls.len();
+            button.set_sensitive(false);
+            choice_list.set_sensitive(false);
+            dialog.set_can_close(false);
+            summary.remove_css_class("error");
+            progress.set_visible(true);
+
+            let widgets = widgets.clone();
+            let state = state.clone();
+            let settings = settings.clone();
+            let pending = pending.clone();
+            let button = button.clone();
+            glib::spawn_future_local(async move {
+                let mut added = 0;
+                let mut failure = None;
+                for chunk in urls.chunks(IMPORT_CHUNK_SIZE) {
+                    progress.set_fraction(added as f64 / total as f64);
+                    progress.set_text(Some(&format!("Added {added} of {total}")));
+                    let api_url = settings.borrow().api_url.clone();
+                    let chunk = chunk.to_vec();
+                    let count = chunk.len();
+                    let result = gio::spawn_blocking(move || api::add_urls(&api_url, &chunk))
+                        .await
+                        .unwrap_or_else(|_| {
+                            Err(ApiError::Transport("worker thread panicked".into()))
+                        });
+                    match result {
+                        Ok(_) => added += count,
+                        Err(e) => {
+                            failure = Some(e);
+                            break;
+                        }
+                    }
+                }
+                dialog.set_can_close(true);
+                if added > 0 {
+                    trigger_refresh(&state, &widgets, &settings);
+                }
+
+                match failure {
+                    None => {
+                        widgets.show_toast(&format!("Added {added} URL(s)"));
+                        dialog.close();
+                    }
+                    Some(e) => {
+                        // Keep the dialog open so the rest can be retried
+                        let remaining = urls[added..].to_vec();
+                        progress.set_fraction(added as f64 / total as f64);
+                        progress.set_text(Some(&format!("Added {added} of {total}")));
+                        summary.set_label(&format!("Stopped: {e}"));
+                        summary.add_css_class("error");
+                        button.set_label(&format!("Retry Remaining {}", remaining.len()));
+                        button.set_sensitive(true);
+                        *pending.borrow_mut() = remaining;
+                    }
+                }
+            });
+        }
+    ));
+
+    dialog.present(Some(window));
+}
+
+/// Ask which downloads to export and in what format, then where to save them
+fn show_export_dialog(
+    window: &adw::ApplicationWindow,
+    widgets: &Rc<Widgets>,
+    state: &Rc<RefCell<AppState>>,
+) {
+    let (shown, total) = {
+        let s = state.borrow();
+        (s.filtered_downloads().len(), s.downloads.len())
+    };
+    if total == 0 {
+        widgets.show_toast("No downloads to export");
+        return;
+    }
+
+    let dialog = adw::AlertDialog::new(Some("Export Downloads"), None);
+
+    let scope_row = adw::ComboRow::builder()
+        .title("Downloads")
+        .model(&gtk::StringList::new(&[
+            &format!("Current view ({shown})"),
+            &format!("All downloads ({total})"),
+        ]))
+        .build();
+    let format_row = adw::ComboRow::builder()
+        .title("Format")
+        .model(&gtk::StringList::new(
+            &export::ExportFormat::ALL
+                .iter()
+                .map(|f| f.label())
+                .collect::<Vec<_>>(),
+        ))
+        .build();
+    let list = gtk::ListBox::builder()
+        .selection_mode(gtk::SelectionMode::None)
+        .css_classes(["boxed-list"])
+        .build();
+    list.append(&scope_row);
+    list.append(&format_row);
+    dialog.set_extra_child(Some(&list));
+
+    dialog.add_responses(&[("cancel", "_Cancel"), ("export", "_Export…")]);
+    dialog.set_response_appearance("export", adw::ResponseAppearance::Suggested);
+    dialog.set_default_response(Some("export"));
+    dialog.set_close_response("cancel");
+
+    dialog.connect_response(
+        Some("export"),
+        clone!(
+            #[weak]
+            window,
+            #[strong]
+            widgets,
+            #[strong]
+            state,
+            move |_, _| {
+                let format = export::ExportFormat::ALL[format_row.selected() as usize];
+                // Snapshot now, so the file matches what was on screen
+                let (count, contents) = {
+                    let s = state.borrow();
+                    let downloads: Vec<&Download> = if scope_row.selected() == 1 {
+                        s.downloads.iter().collect()
+                    } else {
+                        s.filtered_downloads()
+                    };
+                    (downloads.len(), format.render(&downloads))
+                };
+                save_export(&window, format, count, contents, &widgets);
+            }
+        ),
+    );
+
+    dialog.present(Some(window));
+}
+
+/// Ask where to save an export and write it
+fn save_export(
+    window: &adw::ApplicationWindow,
+    format: export::ExportFormat,
+    count: usize,
+    contents: String,
+    widgets: &Rc<Widgets>,
+) {
+    let dialog = gtk::FileDialog::builder()
+        .title("Export Downloads")
+        .initial_name(format!("downloads.{}", format.extension()))
+        .build();
+
+    let window = window.clone();
+    let widgets = widgets.clone();
+    glib::spawn_future_local(async move {
+        let Ok(file) = dialog.save_future(Some(&window)).await else {
+            return; // Cancelled
+        };
+        let Some(path) = file.path() else {
+            widgets.show_toast("Exports can only be saved to local files");
+            return;
+        };
+        let name = file
+            .basename()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        let result = gio::spawn_blocking(move || std::fs::write(&path, contents))
+            .await
+            .unwrap_or_else(|_| Err(std::io::Error::other("worker thread panicked")));
+        match result {
+            Ok(()) => widgets.show_toast(&format!("Exported {count} download(s) to {name}")),
+            Err(e) => widgets.show_toast(&format!("Could not write {name}: {e}")),
+        }
+    });
+}
+
+/// Snapshot every download on the server to a backup file
+fn back_up_queue(
+    window: &adw::ApplicationWindow,
+    widgets: &Rc<Widgets>,
+    settings: &Rc<RefCell<AppSettings>>,
+) {
+    let api_url = settings.borrow().api_url.clone();
+    let now = glib::DateTime::now_utc().ok();
+    let created_at = now
+        .as_ref()
+        .and_then(|t| t.format_iso8601().ok())
+        .map(|t| t.to_string())
+        .unwrap_or_default();
+    let date = now
+        .as_ref()
+        .and_then(|t| t.format("%Y-%m-%d").ok())
+        .map(|d| format!("-{d}"))
+        .unwrap_or_default();
+
+    let window = window.clone();
+    let widgets = widgets.clone();
+    glib::spawn_future_local(async move {
+        // Fetch afresh so the backup is complete even if the list is stale
+        let fetch_url = api_url.clone();
+        let downloads = gio::spawn_blocking(move || api::fetch_downloads(&fetch_url))
+            .await
+            .unwrap_or_else(|_| Err(ApiError::Transport("worker thread panicked".into())));
+        let downloads = match downloads {
+            Ok(downloads) => downloads,
+            Err(e) => {
+                widgets.show_toast(&format!("Backup failed: {e}"));
+                return;
+            }
+        };
+        let count = downloads.len();
+        let contents = backup::Backup::new(&api_url, &created_at, downloads).to_json();
+
+        let dialog = gtk::FileDialog::builder()
+            .title("Back Up Queue")
+            .initial_name(format!("dlm-backup{date}.json"))
+            .build();
+        let Ok(file) = dialog.save_future(Some(&window)).await else {
+            return; // Cancelled
+        };
+        let Some(path) = file.path() else {
+            widgets.show_toast("Backups can only be saved to local files");
+            return;
+        };
+        let name = file
+            .basename()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let result = gio::spawn_blocking(move || std::fs::write(&path, contents))
+            .await
+            .unwrap_or_else(|_| Err(std::io::Error::other("worker thread panicked")));
+        match result {
+            Ok(()) => widgets.show_toast(&format!("Backed up {count} download(s) to {name}")),
+            Err(e) => widgets.show_toast(&format!("Could not write {name}: {e}")),
+        }
+    });
+}
+
+/// Pick a backup file and preview restoring its unfinished downloads to the
+/// current server, leaving out URLs the server already has
+fn restore_queue(
+    window: &adw::ApplicationWindow,
+    widgets: &Rc<Widgets>,
+    state: &Rc<RefCell<AppState>>,
+    settings: &Rc<RefCell<AppSettings>>,
+) {
+    let json = gtk::FileFilter::new();
+    json.set_name(Some("Queue backups"));
+    json.add_suffix("json");
+    let filters = gio::ListStore::new::<gtk::FileFilter>();
+    filters.append(&json);
+    let dialog = gtk::FileDialog::builder()
+        .title("Restore Queue")
+        .filters(&filters)
+        .build();
+
+    let window = window.clone();
+    let widgets = widgets.clone();
+    let state = state.clone();
+    let settings = settings.clone();
+    glib::spawn_future_local(async move {
+        let Ok(file) = dialog.open_future(Some(&window)).await else {
+            return; // Cancelled
+        };
+        let Some(path) = file.path() else {
+            widgets.show_toast("Only local files can be restored");
+            return;
+        };
+        let name = file
+            .basename()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        // Read the backup, and what the target server has now
+        let api_url = settings.borrow().api_url.clone();
+        let target = api_url.clone();
+        let loaded = gio::spawn_blocking(move || {
+            let contents = std::fs::read_to_string(&path).map_err(|e| e.to_string())?;
+            let backup = backup::Backup::parse(&contents)?;
+            let existing = api::fetch_downloads(&target).map_err(|e| e.to_string())?;
+            let config = api::fetch_config(&target).map_err(|e| e.to_string())?;
+            Ok::<_, String>((backup, existing, config))
+        })
+        .await
+        .unwrap_or_else(|_| Err("worker thread panicked".into()));
+        let (backup, existing, config) = match loaded {
+            Ok(loaded) => loaded,
+            Err(e) => {
+                widgets.show_toast(&format!("Could not restore {name}: {e}"));
+                return;
+            }
+        };
+
+        let plan = backup::RestorePlan::new(&backup, &existing, &config);
+        let note = format!(
+            "Backed up from {} at {}. Restoring to {api_url}: {}",
+            backup.server,
+            backup.created_at,
+            plan.summary()
+        );
+        show_import_dialog(
+            &window,
+            &format!("Restore {name}"),
+            Some(&note),
+            import::Source::Urls(plan.to_add),
+            &widgets,
+            &state,
+            &settings,
+        );
+    });
+}
+
+/// Ask for a name and save the Downloads page's current filter and sort
+/// order as a sidebar view, replacing one of the same name
+fn show_save_view_dialog(
+    window: &adw::ApplicationWindow,
+    state: &Rc<RefCell<AppState>>,
+    widgets: &Rc<Widgets>,
+    settings: &Rc<RefCell<AppSettings>>,
+) {
+    let (query, sort) = {
+        let s = state.borrow();
+        (s.current_query(), s.sort_order)
+    };
+    let body = if query.is_empty() {
+        "Shows all downloads".to_string()
+    } else {
+        format!("Shows downloads matching “{query}”")
+    };
+    let dialog = adw::AlertDialog::new(Some("Save View"), Some(&body));
+
+    let name = match selected_sidebar_item(widgets) {
+        Some(SidebarItem::View(view)) => view.name,
+        _ => String::new(),
+    };
+    let name_row = adw::EntryRow::builder()
+        .title("Name")
+        .text(&name)
+        .activates_default(true)
+        .build();
+    let list = gtk::ListBox::builder()
+        .selection_mode(gtk::SelectionMode::None)
+        .css_classes(["boxed-list"])
+        .build();
+    list.append(&name_row);
+    dialog.set_extra_child(Some(&list));
+
+    dialog.add_responses(&[("cancel", "_Cancel"), ("save", "_Save")]);
+    dialog.set_response_appearance("save", adw::ResponseAppearance::Suggested);
+    dialog.set_default_response(Some("save"));
+    dialog.set_close_response("cancel");
+
+    let update_response = clone!(
+        #[weak]
+        dialog,
+        #[strong]
+        settings,
+        move |row: &adw::EntryRow| {
+            let name = row.text();
+            let name = name.trim();
+            let exists = settings.borrow().saved_views.iter().any(|v| v.name == name);
+            dialog.set_response_enabled("save", !name.is_empty());
+            dialog.set_response_label("save", if exists { "_Replace" } else { "_Save" });
+        }
+    );
+    update_response(&name_row);
+    name_row.connect_changed(update_response);
+
+    dialog.connect_response(
+        Some("save"),
+        clone!(
+            #[strong]
+            name_row,
+            #[strong]
+            state,
+            #[strong]
+            widgets,
+            #[strong]
+            settings,
+            move |_, _| {
+                let view = SavedView {
+                    name: name_row.text().trim().to_string(),
+                    query: query.clone(),
+                    sort,
+                };
+                if view.name.is_empty() {
+                    return;
+                }
+                let key = SidebarItem::View(view.clone()).key();
+                edit_saved_views(&state, &widgets, &settings, |views| {
+                    match views.iter_mut().find(|v| v.name == view.name) {
+                        Some(existing) => *existing = view,
+                        None => views.push(view),
+                    }
+                });
+                select_sidebar_item(&widgets, &key);
+            }
+        ),
+    );
+
+    name_row.grab_focus();
+    dialog.present(Some(window));
+}
+
+/// Remove a saved view, offering to undo
+fn remove_saved_view(
+    name: &str,
+    state: &Rc<RefCell<AppState>>,
+    widgets: &Rc<Widgets>,
+    settings: &Rc<RefCell<AppSettings>>,
+) {
+    let Some(index) = settings
+        .borrow()
+        .saved_views
+        .iter()
+        .position(|v| v.name == name)
+    else {
+        return;
+    };
+    let mut removed = None;
+    edit_saved_views(state, widgets, settings, |views| {
+        removed = Some(views.remove(index));
+    });
 
-            glib::spawn_future_local(async move {
-                let result = gio::spawn_blocking(move || api::add_urls(&api_url, &urls))
-                    .await
-                    .unwrap_or_else(|_| Err("Thread error".into()));
-                match result {
-                    Ok(msg) => {
-                        widgets2.show_toast(&format!("Added {count} URL(s): {msg}"));
-                        trigger_refresh(&state2, &widgets2, &settings2);
-                    }
-                    Err(_) => widgets2.show_toast("Failed to add URLs"),
+    let toast = adw::Toast::builder()
+        .title(format!("Removed view “{name}”"))
+        .button_label("Undo")
+        .build();
+    toast.set_use_markup(false);
+    toast.connect_button_clicked(clone!(
+        #[strong]
+        state,
+        #[strong]
+        widgets,
+        #[strong]
+        settings,
+        move |_| {
+            let Some(view) = removed.clone() else {
+                return;
+            };
+            edit_saved_views(&state, &widgets, &settings, |views| {
+                if !views.iter().any(|v| v.name == view.name) {
+                    views.insert(index.min(views.len()), view);
                 }
             });
-
-            dialog.close();
         }
     ));
+    widgets.toast_overlay.add_toast(toast);
+}
 
-    dialog.present(Some(window));
+/// Change the saved views, save the settings and update the sidebar
+fn edit_saved_views(
+    state: &Rc<RefCell<AppState>>,
+    widgets: &Widgets,
+    settings: &Rc<RefCell<AppSettings>>,
+    edit: impl FnOnce(&mut Vec<SavedView>),
+) {
+    let views = {
+        let mut settings = settings.borrow_mut();
+        edit(&mut settings.saved_views);
+        save_settings(&settings);
+        settings.saved_views.clone()
+    };
+    update_sidebar(state, widgets, &views);
+}
+
+/// Post URLs to the server, offering a retry toast if the server is unreachable
+fn submit_urls(
+    urls: Vec<String>,
+    widgets: &Rc<Widgets>,
+    state: &Rc<RefCell<AppState>>,
+    settings: &Rc<RefCell<AppSettings>>,
+) {
+    let count = urls.len();
+    let api_url = settings.borrow().api_url.clone();
+    let widgets = widgets.clone();
+    let state = state.clone();
+    let settings = settings.clone();
+
+    glib::spawn_future_local(async move {
+        let urls2 = urls.clone();
+        let result = gio::spawn_blocking(move || api::add_urls(&api_url, &urls2))
+            .await
+            .unwrap_or_else(|_| Err(ApiError::Transport("worker thread panicked".into())));
+        match result {
+            Ok(msg) => {
+                widgets.show_toast(&format!("Added {count} URL(s): {msg}"));
+                trigger_refresh(&state, &widgets, &settings);
+            }
+            Err(e) => show_api_error(
+                &widgets,
+                &format!("Failed to add {count} URL(s)"),
+                &e,
+                clone!(
+                    #[strong]
+                    widgets,
+                    #[strong]
+                    state,
+                    #[strong]
+                    settings,
+                    move || submit_urls(urls.clone(), &widgets, &state, &settings)
+                ),
+            ),
+        }
+    });
 }
 
 fn show_settings_dialog(
@@ -678,13 +2060,74 @@ fn show_settings_dialog(
         .build();
     server_group.add(&url_row);
 
+    let notify_group = adw::PreferencesGroup::builder()
+        .title("Notifications")
+        .build();
+
+    let notify_success_row = adw::SwitchRow::builder()
+        .title("Finished downloads")
+        .active(current.notify_success)
+        .build();
+    notify_group.add(&notify_success_row);
+
+    let notify_errors_row = adw::SwitchRow::builder()
+        .title("Failed downloads")
+        .active(current.notify_errors)
+        .build();
+    notify_group.add(&notify_errors_row);
+
+    let background_group = adw::PreferencesGroup::builder().title("Background").build();
+
+    let background_row = adw::SwitchRow::builder()
+        .title("Keep running in background")
+        .subtitle("Closing the window hides it; refreshes and notifications continue")
+        .active(current.run_in_background)
+        .build();
+    background_group.add(&background_row);
+
+    let autostart_row = adw::SwitchRow::builder()
+        .title("Start on login")
+        .subtitle("Starts hidden in the background")
+        .active(autostart_enabled())
+        .build();
+    autostart_row.connect_active_notify(clone!(
+        #[strong]
+        widgets,
+        move |row| {
+            if let Err(e) = set_autostart(row.is_active()) {
+                widgets.show_toast(&format!("Failed to update autostart entry: {e}"));
+            }
+        }
+    ));
+    background_group.add(&autostart_row);
+
+    let clipboard_group = adw::PreferencesGroup::builder().title("Clipboard").build();
+
+    let clipboard_row = adw::SwitchRow::builder()
+        .title("Offer to queue copied links")
+        .subtitle("When a copied URL matches a collection")
+        .active(current.watch_clipboard)
+        .build();
+    clipboard_group.add(&clipboard_row);
+
     page.add(&server_group);
+    page.add(&notify_group);
+    page.add(&clipboard_group);
+    page.add(&background_group);
     dialog.add(&page);
 
     dialog.connect_closed(clone!(
         #[strong]
         url_row,
         #[strong]
+        notify_success_row,
+        #[strong]
+        notify_errors_row,
+        #[strong]
+        background_row,
+        #[strong]
+        clipboard_row,
+        #[strong]
         settings,
         #[strong]
         widgets,
@@ -692,16 +2135,216 @@ fn show_settings_dialog(
         state,
         move |_| {
             let new_url = url_row.text().to_string();
-            let new_settings = AppSettings { api_url: new_url };
+            let url_changed = settings.borrow().api_url != new_url;
+            let new_settings = AppSettings {
+                api_url: new_url,
+                notify_success: notify_success_row.is_active(),
+                notify_errors: notify_errors_row.is_active(),
+                run_in_background: background_row.is_active(),
+                watch_clipboard: clipboard_row.is_active(),
+                saved_views: settings.borrow().saved_views.clone(),
+                sort_order: settings.borrow().sort_order,
+                group_by: settings.borrow().group_by,
+            };
             save_settings(&new_settings);
             *settings.borrow_mut() = new_settings;
             trigger_refresh(&state, &widgets, &settings);
+            if url_changed {
+                start_event_stream(&state, &widgets, &settings);
+            }
         }
     ));
 
     dialog.present(Some(window));
 }
 
+/// Show every field of a download, the command the server runs for it, and
+/// its recorded runs from `downloads.log` when the collection directory is
+/// readable from here
+fn show_download_details(parent: &impl IsA<gtk::Widget>, dl: &Download, state: &AppState) {
+    let dialog = adw::Dialog::builder()
+        .title(dl.display_title())
+        .content_width(640)
+        .content_height(600)
+        .build();
+
+    let toolbar_view = adw::ToolbarView::new();
+    toolbar_view.add_top_bar(&adw::HeaderBar::new());
+
+    let page = adw::PreferencesPage::new();
+
+    let details = adw::PreferencesGroup::builder().title("Details").build();
+    let fields = [
+        ("Title", dl.display_title().to_string()),
+        ("URL", dl.url.clone()),
+        ("Status", dl.status.to_string()),
+        ("Priority", dl.priority.to_string()),
+        ("Collection", dl.collection.clone()),
+        ("ID", dl.id.to_string()),
+        (
+            "Created",
+            dl.created_at
+                .map(with_relative)
+                .unwrap_or_else(|| "Unknown".into()),
+        ),
+        (
+            "Downloaded",
+            dl.downloaded_at
+                .map(with_relative)
+                .unwrap_or_else(|| "Not yet".into()),
+        ),
+    ]
+    .into_iter()
+    .chain(
+        dl.time_in_queue()
+            .map(|waited| ("Time in Queue", time::duration(waited))),
+    );
+    for (title, value) in fields {
+        let row = adw::ActionRow::builder()
+            .title(title)
+            .subtitle(value)
+            .subtitle_selectable(true)
+            .use_markup(false)
+            .css_classes(["property"])
+            .build();
+        details.add(&row);
+    }
+    page.add(&details);
+
+    if let Some(error) = &dl.error_message {
+        let group = adw::PreferencesGroup::builder().title("Error").build();
+        group.add(&detail_text_label(error, &["error"]));
+        page.add(&group);
+    }
+
+    let collection = state
+        .config
+        .as_ref()
+        .and_then(|c| c.collection(&dl.collection));
+
+    let command_group = adw::PreferencesGroup::builder().title("Command").build();
+    match collection {
+        Some(collection) => {
+            command_group.add(&detail_text_label(
+                &collection.command_for(&dl.url),
+                &["monospace"],
+            ));
+        }
+        None => {
+            command_group.set_description(Some("Collection not found in config"));
+        }
+    }
+    page.add(&command_group);
+
+    // Output from downloads.log, loaded off the main thread
+    if let Some(collection) = collection {
+        let output_group = adw::PreferencesGroup::builder()
+            .title("Output")
+            .description(format!("{}/downloads.log", collection.dir))
+            .visible(false)
+            .build();
+        page.add(&output_group);
+
+        let dir = collection.dir.clone();
+        let id = dl.id;
+        glib::spawn_future_local(async move {
+            let result = gio::spawn_blocking(move || download_log::read_blocks(&dir, id)).await;
+            // Unreadable from here, e.g. the server runs on another machine
+            let Ok(Ok(blocks)) = result else {
+                return;
+            };
+            if blocks.is_empty() {
+                output_group.set_description(Some("No runs recorded yet"));
+            } else {
+                let text: Vec<&str> = blocks.iter().map(|b| b.text.as_str()).collect();
+                let buffer = gtk::TextBuffer::new(None);
+                buffer.set_text(&text.join("\n\n"));
+                let view = gtk::TextView::builder()
+                    .buffer(&buffer)
+                    .editable(false)
+                    .monospace(true)
+                    .wrap_mode(gtk::WrapMode::WordChar)
+                    .css_classes(["card"])
+                    .top_margin(8)
+                    .bottom_margin(8)
+                    .left_margin(8)
+                    .right_margin(8)
+                    .build();
+                output_group.add(&view);
+            }
+            output_group.set_visible(true);
+        });
+    }
+
+    // Actions, routed through the same app.* actions as the row buttons
+    let actions = gtk::Box::builder()
+        .orientation(gtk::Orientation::Horizontal)
+        .spacing(6)
+        .halign(gtk::Align::Center)
+        .margin_top(6)
+        .margin_bottom(6)
+        .build();
+
+    let open_btn = gtk::Button::builder()
+        .label("Open Folder")
+        .css_classes(["pill"])
+        .action_name("app.open-folder")
+        .action_target(&dl.collection.to_variant())
+        .build();
+    actions.append(&open_btn);
+
+    for &action in DownloadAction::ALL {
+        if !action.applies_to(&dl.status) {
+            continue;
+        }
+        let btn = gtk::Button::builder()
+            .label(action.label())
+            .css_classes(["pill"])
+            .action_name(format!("app.{}", action.name()))
+            .action_target(&dl.id.to_variant())
+            .build();
+        if action == DownloadAction::Delete {
+            btn.add_css_class("destructive-action");
+        }
+        actions.append(&btn);
+    }
+
+    let mut child = actions.first_child();
+    while let Some(btn) = child.and_downcast::<gtk::Button>() {
+        btn.connect_clicked(clone!(
+            #[weak]
+            dialog,
+            move |_| {
+                dialog.close();
+            }
+        ));
+        child = btn.next_sibling();
+    }
+
+    toolbar_view.add_bottom_bar(&actions);
+    toolbar_view.set_content(Some(&page));
+    dialog.set_child(Some(&toolbar_view));
+    dialog.present(Some(parent));
+}
+
+/// Local date and time, and how long ago that was
+fn with_relative(at: Timestamp) -> String {
+    format!("{} ({})", at.local(), time::relative(at, Timestamp::now()))
+}
+
+/// Selectable, wrapping block of text for the details dialog
+fn detail_text_label(text: &str, css_classes: &[&str]) -> gtk::Label {
+    let label = gtk::Label::builder()
+        .label(text)
+        .wrap(true)
+        .wrap_mode(gtk::pango::WrapMode::WordChar)
+        .selectable(true)
+        .xalign(0.0)
+        .build();
+    label.set_css_classes(css_classes);
+    label
+}
+
 fn show_keyboard_help(window: &adw::ApplicationWindow) {
     let dialog = adw::Dialog::builder()
         .title("Keyboard Shortcuts")
@@ -750,7 +2393,7 @@ fn show_keyboard_help(window: &adw::ApplicationWindow) {
 
 fn do_action(
     api_url_str: &str,
-    action: impl FnOnce(String) -> Result<String, String> + Send + 'static,
+    action: impl Fn(String) -> Result<String, ApiError> + Clone + Send + 'static,
     widgets: &Rc<Widgets>,
     state: &Rc<RefCell<AppState>>,
     settings: &Rc<RefCell<AppSettings>>,
@@ -761,19 +2404,297 @@ fn do_action(
     let settings = settings.clone();
 
     glib::spawn_future_local(async move {
-        let result = gio::spawn_blocking(move || action(api_url))
+        let action2 = action.clone();
+        let result = gio::spawn_blocking(move || action2(api_url))
             .await
-            .unwrap_or_else(|_| Err("Thread error".into()));
+            .unwrap_or_else(|_| Err(ApiError::Transport("worker thread panicked".into())));
         match result {
             Ok(msg) => {
                 widgets.show_toast(&msg);
                 trigger_refresh(&state, &widgets, &settings);
             }
-            Err(e) => widgets.show_toast(&format!("Error: {e}")),
+            Err(e) => show_api_error(
+                &widgets,
+                "Action failed",
+                &e,
+                clone!(
+                    #[strong]
+                    widgets,
+                    #[strong]
+                    state,
+                    #[strong]
+                    settings,
+                    move || {
+                        let api_url = settings.borrow().api_url.clone();
+                        do_action(&api_url, action.clone(), &widgets, &state, &settings);
+                    }
+                ),
+            ),
+        }
+    });
+}
+
+/// Apply `action` to every one of `downloads` it applies to (the selection,
+/// or a group), one request at a time, then report the outcome in a single
+/// toast. The selection is cleared afterwards if it was what ran.
+fn run_bulk_action(
+    action: DownloadAction,
+    downloads: &[Download],
+    from_selection: bool,
+    widgets: &Rc<Widgets>,
+    state: &Rc<RefCell<AppState>>,
+    settings: &Rc<RefCell<AppSettings>>,
+) {
+    let ids: Vec<i64> = downloads
+        .iter()
+        .filter(|d| action.applies_to(&d.status))
+        .map(|d| d.id)
+        .collect();
+    if ids.is_empty() || state.borrow().bulk_running {
+        return;
+    }
+
+    state.borrow_mut().bulk_running = true;
+    update_selection_bar(&state.borrow(), widgets);
+    widgets.selection_progress.set_fraction(0.0);
+    widgets.selection_progress.set_visible(true);
+
+    let api_url = settings.borrow().api_url.clone();
+    let widgets = widgets.clone();
+    let state = state.clone();
+    let settings = settings.clone();
+
+    glib::spawn_future_local(async move {
+        let total = ids.len();
+        let mut failures = Vec::new();
+        for (done, id) in ids.into_iter().enumerate() {
+            let url = api_url.clone();
+            let result = gio::spawn_blocking(move || api::perform(&url, action, id))
+                .await
+                .unwrap_or_else(|_| Err(ApiError::Transport("worker thread panicked".into())));
+            if let Err(e) = result {
+                failures.push((id, e.to_string()));
+            }
+            widgets
+                .selection_progress
+                .set_fraction((done + 1) as f64 / total as f64);
+        }
+
+        state.borrow_mut().bulk_running = false;
+        widgets.selection_progress.set_visible(false);
+        if from_selection {
+            widgets.downloads_selection.unselect_all();
         }
+
+        let toast = adw::Toast::new(&action.summary(total, &failures));
+        toast.set_use_markup(false);
+        if !failures.is_empty() {
+            toast.set_timeout(0);
+        }
+        widgets.toast_overlay.add_toast(toast);
+        trigger_refresh(&state, &widgets, &settings);
     });
 }
 
+/// Show a toast for a failed API call. Server-side rejections (4xx) show the
+/// server's message as-is; transient failures get a "Retry" button.
+fn show_api_error(widgets: &Widgets, context: &str, err: &ApiError, retry: impl Fn() + 'static) {
+    let title = match err {
+        ApiError::Status { code, .. } if *code < 500 => err.to_string(),
+        _ => format!("{context}: {err}"),
+    };
+    let toast = adw::Toast::new(&title);
+    toast.set_use_markup(false);
+    if err.is_retryable() {
+        toast.set_button_label(Some("Retry"));
+        toast.connect_button_clicked(move |_| retry());
+    }
+    widgets.toast_overlay.add_toast(toast);
+}
+
+/// Register the `app.*` actions used by download rows: one per
+/// [`DownloadAction`] taking the download id, plus `open-folder` taking the
+/// collection name. Group headers use `toggle-group` taking the group key,
+/// and `group-action` taking an action name and group key.
+fn install_download_actions(
+    app: &adw::Application,
+    window: &adw::ApplicationWindow,
+    state: &Rc<RefCell<AppState>>,
+    widgets: &Rc<Widgets>,
+    settings: &Rc<RefCell<AppSettings>>,
+) {
+    for &action in DownloadAction::ALL {
+        let gaction = gio::SimpleAction::new(action.name(), Some(glib::VariantTy::INT64));
+        gaction.connect_activate(clone!(
+            #[strong]
+            state,
+            #[strong]
+            widgets,
+            #[strong]
+            settings,
+            move |_, param| {
+                let Some(id) = param.and_then(|p| p.get::<i64>()) else {
+                    return;
+                };
+                let api_url = settings.borrow().api_url.clone();
+                do_action(
+                    &api_url,
+                    move |url| api::perform(&url, action, id),
+                    &widgets,
+                    &state,
+                    &settings,
+                );
+            }
+        ));
+        app.add_action(&gaction);
+    }
+
+    let open_folder = gio::SimpleAction::new("open-folder", Some(glib::VariantTy::STRING));
+    open_folder.connect_activate(clone!(
+        #[strong]
+        state,
+        #[strong]
+        widgets,
+        move |_, param| {
+            let Some(collection) = param.and_then(|p| p.get::<String>()) else {
+                return;
+            };
+            let dir = state
+                .borrow()
+                .dir_for_collection(&collection)
+                .map(str::to_string);
+            match dir {
+                Some(dir) => {
+                    if let Err(e) = open::that(&dir) {
+                        widgets.show_toast(&format!("Failed to open folder: {e}"));
+                    }
+                }
+                None => widgets.show_toast("Collection directory not found in config"),
+            }
+        }
+    ));
+    app.add_action(&open_folder);
+
+    let toggle_group = gio::SimpleAction::new("toggle-group", Some(glib::VariantTy::STRING));
+    toggle_group.connect_activate(clone!(
+        #[strong]
+        state,
+        #[strong]
+        widgets,
+        move |_, param| {
+            if let Some(key) = param.and_then(|p| p.get::<String>()) {
+                toggle_group_collapsed(&state, &widgets, key);
+            }
+        }
+    ));
+    app.add_action(&toggle_group);
+
+    let group_action = gio::SimpleAction::new(
+        "group-action",
+        Some(&<(String, String)>::static_variant_type()),
+    );
+    group_action.connect_activate(clone!(
+        #[weak]
+        window,
+        #[strong]
+        state,
+        #[strong]
+        widgets,
+        #[strong]
+        settings,
+        move |_, param| {
+            let Some((name, key)) = param.and_then(|p| p.get::<(String, String)>()) else {
+                return;
+            };
+            let Some(&action) = DownloadAction::ALL.iter().find(|a| a.name() == name) else {
+                return;
+            };
+            // Collapsed groups included, though their rows aren't shown
+            let Some((title, downloads)) = state
+                .borrow()
+                .grouped_downloads()
+                .into_iter()
+                .find(|g| g.key == key)
+                .map(|g| {
+                    (
+                        g.title,
+                        g.downloads.into_iter().cloned().collect::<Vec<_>>(),
+                    )
+                })
+            else {
+                return;
+            };
+            if action == DownloadAction::Delete {
+                confirm_group_delete(&window, &title, downloads, &widgets, &state, &settings);
+            } else {
+                run_bulk_action(action, &downloads, false, &widgets, &state, &settings);
+            }
+        }
+    ));
+    app.add_action(&group_action);
+}
+
+/// Ask before deleting every download in a group, since the group may hold
+/// rows that aren't visible
+fn confirm_group_delete(
+    window: &adw::ApplicationWindow,
+    title: &str,
+    downloads: Vec<Download>,
+    widgets: &Rc<Widgets>,
+    state: &Rc<RefCell<AppState>>,
+    settings: &Rc<RefCell<AppSettings>>,
+) {
+    let count = downloads
+        .iter()
+        .filter(|d| DownloadAction::Delete.applies_to(&d.status))
+        .count();
+    let heading = match count {
+        1 => "Delete 1 Download?".to_string(),
+        n => format!("Delete {n} Downloads?"),
+    };
+    let body = format!("Every download in the group “{title}” will be removed.");
+    let dialog = adw::AlertDialog::new(Some(&heading), Some(&body));
+    dialog.add_responses(&[("cancel", "_Cancel"), ("delete", "_Delete")]);
+    dialog.set_response_appearance("delete", adw::ResponseAppearance::Destructive);
+    dialog.set_default_response(Some("cancel"));
+    dialog.set_close_response("cancel");
+
+    dialog.connect_response(
+        Some("delete"),
+        clone!(
+            #[strong]
+            widgets,
+            #[strong]
+            state,
+            #[strong]
+            settings,
+            move |_, _| {
+                run_bulk_action(
+                    DownloadAction::Delete,
+                    &downloads,
+                    false,
+                    &widgets,
+                    &state,
+                    &settings,
+                );
+            }
+        ),
+    );
+
+    dialog.present(Some(window));
+}
+
+/// Collapse the group with `key` on the Downloads page, or expand it
+fn toggle_group_collapsed(state: &Rc<RefCell<AppState>>, widgets: &Widgets, key: String) {
+    {
+        let mut s = state.borrow_mut();
+        if !s.collapsed_groups.remove(&key) {
+            s.collapsed_groups.insert(key);
+        }
+    }
+    update_downloads_list(&state.borrow(), widgets);
+}
+
 // ============================================================================
 // Page Builders
 // ============================================================================
@@ -940,41 +2861,78 @@ fn build_downloads_page(
         .css_classes(["linked"])
         .build();
 
-    let mut first_filter_btn: Option<gtk::ToggleButton> = None;
-    for sf in StatusFilter::ALL_FILTERS {
-        let btn = gtk::ToggleButton::builder().label(sf.label()).build();
-        if let Some(ref first) = first_filter_btn {
+    for (filter, btn) in &widgets.status_buttons {
+        if let Some((_, first)) = widgets.status_buttons.first()
+            && first != btn
+        {
             btn.set_group(Some(first));
-        } else {
-            first_filter_btn = Some(btn.clone());
         }
-        if *sf == StatusFilter::All {
+        if *filter == StatusFilter::All {
             btn.set_active(true);
         }
-        let filter = *sf;
+        let filter = *filter;
         btn.connect_toggled(clone!(
             #[strong]
             state,
             #[strong]
             widgets,
-            #[strong]
-            settings,
             move |btn| {
                 if btn.is_active() {
                     state.borrow_mut().status_filter = filter;
-                    update_downloads_list(&state, &widgets, &settings);
+                    update_downloads_list(&state.borrow(), &widgets);
                 }
             }
         ));
-        filter_buttons.append(&btn);
+        filter_buttons.append(btn);
+    }
+
+    // Sort dropdown
+    let sort_dropdown = &widgets.downloads_sort;
+    show_sort_order(widgets, state.borrow().sort_order);
+    sort_dropdown.set_tooltip_text(Some("Sort by"));
+    sort_dropdown.connect_selected_notify(clone!(
+        #[strong]
+        state,
+        #[strong]
+        widgets,
+        #[strong]
+        settings,
+        move |dd| {
+            let Some(&key) = SortKey::ALL.get(dd.selected() as usize) else {
+                return;
+            };
+            // Also notified when a view's order is shown, already applied
+            if state.borrow().sort_order.key != key {
+                set_sort_order(&state, &widgets, &settings, SortOrder::new(key));
+            }
+        }
+    ));
+    widgets.downloads_sort_direction.connect_clicked(clone!(
+        #[strong]
+        state,
+        #[strong]
+        widgets,
+        #[strong]
+        settings,
+        move |_| {
+            let order = state.borrow().sort_order.reversed();
+            set_sort_order(&state, &widgets, &settings, order);
+        }
+    ));
+    let sort_box = gtk::Box::builder().css_classes(["linked"]).build();
+    sort_box.append(sort_dropdown);
+    sort_box.append(&widgets.downloads_sort_direction);
+
+    // Group dropdown
+    let group_dropdown = &widgets.downloads_group;
+    if let Some(index) = GroupBy::ALL
+        .iter()
+        .position(|&g| g == state.borrow().group_by)
+    {
+        group_dropdown.set_selected(index as u32);
     }
-
-    // Sort dropdown
-    let sort_labels: Vec<&str> = SortOrder::ALL.iter().map(|s| s.label()).collect();
-    let sort_dropdown = gtk::DropDown::from_strings(&sort_labels);
-    sort_dropdown.set_selected(0); // NewestFirst
-    sort_dropdown.set_tooltip_text(Some("Sort order"));
-    sort_dropdown.connect_selected_notify(clone!(
+    group_dropdown.set_tooltip_text(Some("Group by"));
+    group_dropdown.connect_selected_notify(clone!(
         #[strong]
         state,
         #[strong]
@@ -982,11 +2940,17 @@ fn build_downloads_page(
         #[strong]
         settings,
         move |dd| {
-            let idx = dd.selected() as usize;
-            if let Some(&order) = SortOrder::ALL.get(idx) {
-                state.borrow_mut().sort_order = order;
-                update_downloads_list(&state, &widgets, &settings);
+            let Some(&group_by) = GroupBy::ALL.get(dd.selected() as usize) else {
+                return;
+            };
+            {
+                let mut s = state.borrow_mut();
+                s.group_by = group_by;
+                s.collapsed_groups.clear();
             }
+            settings.borrow_mut().group_by = group_by;
+            save_settings(&settings.borrow());
+            update_downloads_list(&state.borrow(), &widgets);
         }
     ));
 
@@ -996,16 +2960,87 @@ fn build_downloads_page(
     widgets.downloads_count_label.set_hexpand(true);
     widgets.downloads_count_label.set_halign(gtk::Align::End);
 
+    let search_btn = gtk::ToggleButton::builder()
+        .icon_name("system-search-symbolic")
+        .tooltip_text("Search (Ctrl+F)")
+        .build();
+    search_btn
+        .bind_property(
+            "active",
+            &widgets.downloads_search_bar,
+            "search-mode-enabled",
+        )
+        .bidirectional()
+        .sync_create()
+        .build();
+
+    let save_view_btn = gtk::Button::builder()
+        .icon_name("bookmark-new-symbolic")
+        .tooltip_text("Save as View (Ctrl+S)")
+        .action_name("app.save-view")
+        .build();
+
     filter_bar.append(&filter_buttons);
-    filter_bar.append(&sort_dropdown);
+    filter_bar.append(&sort_box);
+    filter_bar.append(group_dropdown);
+    filter_bar.append(&search_btn);
+    filter_bar.append(&save_view_btn);
     filter_bar.append(&widgets.downloads_count_label);
     content.append(&filter_bar);
 
-    // Downloads list
-    widgets.downloads_list.set_css_classes(&["boxed-list"]);
+    // Search (Ctrl+F), applied on top of the status filter
+    widgets
+        .downloads_search
+        .set_placeholder_text(Some("Search, or filter with status: collection: domain: …"));
+    widgets
+        .downloads_search
+        .set_tooltip_text(Some(SEARCH_SYNTAX_HELP));
+    widgets.downloads_search.set_hexpand(true);
+    widgets
+        .downloads_search_error
+        .set_css_classes(&["error", "caption"]);
+    widgets.downloads_search_error.set_halign(gtk::Align::Start);
+    widgets.downloads_search_error.set_wrap(true);
+    widgets.downloads_search_error.set_visible(false);
+    let search_box = gtk::Box::builder()
+        .orientation(gtk::Orientation::Vertical)
+        .spacing(4)
+        .build();
+    search_box.append(&widgets.downloads_search);
+    search_box.append(&widgets.downloads_search_error);
+    widgets.downloads_search_bar.set_child(Some(&search_box));
+    widgets
+        .downloads_search_bar
+        .connect_entry(&widgets.downloads_search);
+    widgets.downloads_search_bar.set_show_close_button(true);
+    widgets.downloads_search.connect_search_changed(clone!(
+        #[strong]
+        state,
+        #[strong]
+        widgets,
+        move |entry| {
+            state.borrow_mut().download_search = entry.text().to_string();
+            update_downloads_list(&state.borrow(), &widgets);
+        }
+    ));
+    widgets
+        .downloads_search_bar
+        .connect_search_mode_enabled_notify(clone!(
+            #[strong]
+            widgets,
+            move |bar| {
+                if !bar.is_search_mode() {
+                    widgets.downloads_search.set_text("");
+                }
+            }
+        ));
+    content.append(&widgets.downloads_search_bar);
+
+    // Downloads list, or a status page when there is nothing to show
     widgets
         .downloads_list
-        .set_selection_mode(gtk::SelectionMode::None);
+        .set_css_classes(&["card", "download-list"]);
+    widgets.downloads_list.set_show_separators(true);
 
     let scroll = gtk::ScrolledWindow::builder()
         .hscrollbar_policy(gtk::PolicyType::Never)
@@ -1014,7 +3049,20 @@ fn build_downloads_page(
         .vexpand(true)
         .build();
 
-    content.append(&scroll);
+    widgets
+        .downloads_empty
+        .set_icon_name(Some("folder-download-symbolic"));
+    widgets.downloads_empty.set_title("No Downloads");
+
+    widgets
+        .downloads_stack
+        .add_named(&widgets.downloads_empty, Some("empty"));
+    widgets.downloads_stack.add_named(&scroll, Some("list"));
+    widgets.downloads_stack.set_visible_child_name("empty");
+    widgets.downloads_stack.set_vexpand(true);
+
+    content.append(&widgets.downloads_stack);
+    content.append(&build_selection_bar(widgets, state, settings));
 
     let clamp = adw::Clamp::builder()
         .maximum_size(900)
@@ -1028,6 +3076,115 @@ fn build_downloads_page(
     page
 }
 
+/// A sidebar row: icon, title and count badge, plus a remove button for
+/// saved views. Returns the row and its count label.
+fn build_sidebar_row(item: &SidebarItem) -> (gtk::ListBoxRow, gtk::Label) {
+    let (title, icon) = sidebar_title_and_icon(item);
+    let row_box = gtk::Box::builder()
+        .orientation(gtk::Orientation::Horizontal)
+        .spacing(12)
+        .build();
+    row_box.append(&gtk::Image::from_icon_name(icon));
+    row_box.append(
+        &gtk::Label::builder()
+            .label(&title)
+            .xalign(0.0)
+            .hexpand(true)
+            .ellipsize(gtk::pango::EllipsizeMode::End)
+            .build(),
+    );
+    let count = gtk::Label::builder()
+        .css_classes(["dim-label", "caption", "numeric"])
+        .build();
+    row_box.append(&count);
+
+    let row = gtk::ListBoxRow::builder().child(&row_box).build();
+    if let SidebarItem::View(view) = item {
+        let remove_btn = gtk::Button::builder()
+            .icon_name("user-trash-symbolic")
+            .tooltip_text("Remove View")
+            .css_classes(["flat", "circular"])
+            .valign(gtk::Align::Center)
+            .build();
+        remove_btn.set_action_target_value(Some(&view.name.to_variant()));
+        remove_btn.set_action_name(Some("app.remove-view"));
+        row_box.append(&remove_btn);
+        if !view.query.is_empty() {
+            row.set_tooltip_text(Some(&view.query));
+        }
+    }
+    (row, count)
+}
+
+/// Action bar shown while downloads are selected, with bulk actions
+fn build_selection_bar(
+    widgets: &Rc<Widgets>,
+    state: &Rc<RefCell<AppState>>,
+    settings: &Rc<RefCell<AppSettings>>,
+) -> gtk::ActionBar {
+    let bar = &widgets.selection_bar;
+    bar.set_revealed(false);
+
+    let clear_btn = gtk::Button::builder()
+        .icon_name("edit-clear-symbolic")
+        .tooltip_text("Clear selection")
+        .css_classes(["flat"])
+        .build();
+    clear_btn.connect_clicked(clone!(
+        #[strong]
+        widgets,
+        move |_| {
+            widgets.downloads_selection.unselect_all();
+        }
+    ));
+
+    let status_box = gtk::Box::builder()
+        .orientation(gtk::Orientation::Vertical)
+        .spacing(4)
+        .valign(gtk::Align::Center)
+        .build();
+    status_box.append(&widgets.selection_label);
+    widgets.selection_progress.set_visible(false);
+    status_box.append(&widgets.selection_progress);
+
+    bar.pack_start(&clear_btn);
+    bar.pack_start(&status_box);
+
+    for (action, button) in widgets.selection_buttons.iter().rev() {
+        if *action == DownloadAction::Delete {
+            button.add_css_class("destructive-action");
+        }
+        let action = *action;
+        button.connect_clicked(clone!(
+            #[strong]
+            widgets,
+            #[strong]
+            state,
+            #[strong]
+            settings,
+            move |_| {
+                let selected = selected_downloads(&widgets);
+                run_bulk_action(action, &selected, true, &widgets, &state, &settings);
+            }
+        ));
+        bar.pack_end(button);
+    }
+
+    widgets
+        .downloads_selection
+        .connect_selection_changed(clone!(
+            #[strong]
+            widgets,
+            #[strong]
+            state,
+            move |_, _, _| {
+                update_selection_bar(&state.borrow(), &widgets);
+            }
+        ));
+
+    bar.clone()
+}
+
 fn build_errors_page(
     widgets: &Rc<Widgets>,
     state: &Rc<RefCell<AppState>>,
@@ -1106,10 +3263,10 @@ fn build_errors_page(
         .description("All downloads are running smoothly.")
         .build();
 
-    widgets.errors_list.set_css_classes(&["boxed-list"]);
     widgets
         .errors_list
-        .set_selection_mode(gtk::SelectionMode::None);
+        .set_css_classes(&["card", "download-list"]);
+    widgets.errors_list.set_show_separators(true);
 
     let error_scroll = gtk::ScrolledWindow::builder()
         .hscrollbar_policy(gtk::PolicyType::Never)
@@ -1163,7 +3320,7 @@ fn build_logs_page(widgets: &Rc<Widgets>, state: &Rc<RefCell<AppState>>) -> gtk:
     for (label, filter) in [
         ("All", LogFilter::All),
         ("Errors", LogFilter::Errors),
-        ("Warnings", LogFilter::Warnings),
+        ("Debug", LogFilter::Debug),
         ("Info", LogFilter::Info),
     ] {
         let btn = gtk::ToggleButton::builder().label(label).build();
@@ -1229,6 +3386,57 @@ fn build_logs_page(widgets: &Rc<Widgets>, state: &Rc<RefCell<AppState>>) -> gtk:
         .right_margin(8)
         .vexpand(true)
         .build();
+    create_log_tags(&widgets.logs_buffer);
+
+    // Clicking a download id jumps to the download, clicking a URL opens it;
+    // not when the click ends a text selection
+    let click = gtk::GestureClick::new();
+    click.connect_released(clone!(
+        #[strong]
+        state,
+        #[strong]
+        widgets,
+        move |gesture, _, x, y| {
+            let Some(view) = gesture.widget().and_downcast::<gtk::TextView>() else {
+                return;
+            };
+            let buffer = view.buffer();
+            if buffer.has_selection() {
+                return;
+            }
+            let (bx, by) =
+                view.window_to_buffer_coords(gtk::TextWindowType::Widget, x as i32, y as i32);
+            let Some(iter) = view.iter_at_location(bx, by) else {
+                return;
+            };
+            if let Some(id) = tagged_text(&buffer, &iter, LOG_ID_TAG).and_then(|t| t.parse().ok()) {
+                reveal_download(&state, &widgets, id);
+            } else if let Some(url) = tagged_text(&buffer, &iter, LOG_URL_TAG)
+                && let Err(e) = open::that(&url)
+            {
+                widgets.show_toast(&format!("Failed to open link: {e}"));
+            }
+        }
+    ));
+    text_view.add_controller(click);
+
+    // Pointer cursor over what can be clicked
+    let motion = gtk::EventControllerMotion::new();
+    motion.connect_motion(|controller, x, y| {
+        let Some(view) = controller.widget().and_downcast::<gtk::TextView>() else {
+            return;
+        };
+        let (bx, by) =
+            view.window_to_buffer_coords(gtk::TextWindowType::Widget, x as i32, y as i32);
+        let clickable = view.iter_at_location(bx, by).is_some_and(|iter| {
+            let buffer = view.buffer();
+            [LOG_ID_TAG, LOG_URL_TAG]
+                .iter()
+                .any(|tag| tagged_text(&buffer, &iter, tag).is_some())
+        });
+        view.set_cursor_from_name(Some(if clickable { "pointer" } else { "text" }));
+    });
+    text_view.add_controller(motion);
 
     let scroll = gtk::ScrolledWindow::builder()
         .hscrollbar_policy(gtk::PolicyType::Never)
@@ -1261,80 +3469,538 @@ fn build_config_page(widgets: &Rc<Widgets>) -> gtk::Box {
         .margin_bottom(12)
         .build();
 
-    let clamp = adw::Clamp::builder()
-        .maximum_size(800)
-        .child(&scroll)
-        .vexpand(true)
-        .build();
+    let clamp = adw::Clamp::builder()
+        .maximum_size(800)
+        .child(&scroll)
+        .vexpand(true)
+        .build();
+
+    page.append(&clamp);
+    page
+}
+
+// ============================================================================
+// Keyboard Handling
+// ============================================================================
+
+fn handle_key_press(
+    key: gtk::gdk::Key,
+    modifier: gtk::gdk::ModifierType,
+    state: &Rc<RefCell<AppState>>,
+    widgets: &Rc<Widgets>,
+    settings: &Rc<RefCell<AppSettings>>,
+    window: &adw::ApplicationWindow,
+) -> bool {
+    let ctrl = modifier.contains(gtk::gdk::ModifierType::CONTROL_MASK);
+
+    match key {
+        gtk::gdk::Key::r if ctrl => {
+            trigger_refresh(state, widgets, settings);
+            widgets.show_toast("Refreshing...");
+            true
+        }
+        gtk::gdk::Key::d if ctrl => {
+            let api_url = settings.borrow().api_url.clone();
+            do_action(
+                &api_url,
+                |url| api::start_downloads(&url, 3),
+                widgets,
+                state,
+                settings,
+            );
+            true
+        }
+        gtk::gdk::Key::n if ctrl => {
+            show_add_urls_dialog(window, widgets, state, settings, &[]);
+            true
+        }
+        gtk::gdk::Key::f if ctrl => {
+            if widgets.logs_search.is_mapped() {
+                widgets.logs_search.grab_focus();
+            } else {
+                if let Some(app) = window.application() {
+                    app.activate_action("show-page", Some(&"downloads".to_variant()));
+                }
+                widgets.downloads_search_bar.set_search_mode(true);
+                widgets.downloads_search.grab_focus();
+            }
+            true
+        }
+        gtk::gdk::Key::Escape if !widgets.downloads_selection.selection().is_empty() => {
+            widgets.downloads_selection.unselect_all();
+            true
+        }
+        gtk::gdk::Key::question => {
+            show_keyboard_help(window);
+            true
+        }
+        _ => false,
+    }
+}
+
+// ============================================================================
+// UI Building
+// ============================================================================
+
+/// Offer to queue a link copied in another app, if enabled in settings
+fn check_clipboard(
+    window: &adw::ApplicationWindow,
+    state: &Rc<RefCell<AppState>>,
+    widgets: &Rc<Widgets>,
+    settings: &Rc<RefCell<AppSettings>>,
+) {
+    let clipboard = window.clipboard();
+    if !settings.borrow().watch_clipboard || clipboard.is_local() {
+        return;
+    }
+    let window = window.clone();
+    let state = state.clone();
+    let widgets = widgets.clone();
+    let settings = settings.clone();
+
+    glib::spawn_future_local(async move {
+        let Ok(Some(text)) = clipboard.read_text_future().await else {
+            return;
+        };
+        let Some((url, collection)) =
+            clipboard::offer_for(&state.borrow(), &text).map(|(u, c)| (u, c.to_string()))
+        else {
+            return;
+        };
+        state.borrow_mut().clipboard_offered = Some(url.clone());
+
+        if window.is_visible() {
+            let toast = adw::Toast::builder()
+                .title(clipboard::toast_title(&url, &collection))
+                .button_label("Add")
+                .timeout(10)
+                .build();
+            toast.set_use_markup(false);
+            toast.connect_button_clicked(clone!(
+                #[strong]
+                widgets,
+                move |_| {
+                    submit_urls(vec![url.clone()], &widgets, &state, &settings);
+                }
+            ));
+            widgets.toast_overlay.add_toast(toast);
+        } else if let Some(app) = window.application() {
+            let notification = gio::Notification::new(&format!("Queue in {collection}?"));
+            notification.set_body(Some(&url));
+            notification.add_button_with_target_value(
+                "Add",
+                "app.add-urls",
+                Some(&vec![url].to_variant()),
+            );
+            app.send_notification(Some(clipboard::NOTIFICATION_ID), &notification);
+        }
+    });
+}
+
+/// Largest dropped file read for links; bigger ones aren't link lists
+const MAX_DROPPED_FILE_BYTES: u64 = 1024 * 1024;
+
+/// URLs in something dropped on the window: links or text from a browser,
+/// or `.txt`/`.url`/`.desktop` files
+fn dropped_urls(value: &glib::Value) -> Vec<String> {
+    let mut urls = Vec::new();
+    if let Ok(files) = value.get::<gtk::gdk::FileList>() {
+        for file in files.files() {
+            match file.path() {
+                Some(path) => {
+                    let small = std::fs::metadata(&path)
+                        .is_ok_and(|m| m.is_file() && m.len() <= MAX_DROPPED_FILE_BYTES);
+                    if small && let Ok(contents) = std::fs::read_to_string(&path) {
+                        urls.extend(links::urls_from_file(&path, &contents));
+                    }
+                }
+                // Links dragged from a browser arrive as non-local files
+                None => urls.extend(links::extract_urls(&file.uri())),
+            }
+        }
+    } else if let Ok(text) = value.get::<String>() {
+        urls = links::extract_urls(&text);
+    }
+
+    let mut seen = HashSet::new();
+    urls.retain(|u| seen.insert(u.clone()));
+    urls
+}
+
+/// Register application-wide actions for the primary menu
+fn install_app_actions(
+    app: &adw::Application,
+    window: &adw::ApplicationWindow,
+    state: &Rc<RefCell<AppState>>,
+    widgets: &Rc<Widgets>,
+    settings: &Rc<RefCell<AppSettings>>,
+) {
+    let quit = gio::SimpleAction::new("quit", None);
+    quit.connect_activate(clone!(
+        #[weak]
+        app,
+        move |_, _| {
+            app.quit();
+        }
+    ));
+    app.add_action(&quit);
+    app.set_accels_for_action("app.quit", &["<Control>q"]);
+
+    let shortcuts = gio::SimpleAction::new("shortcuts", None);
+    shortcuts.connect_activate(clone!(
+        #[weak]
+        window,
+        move |_, _| {
+            show_keyboard_help(&window);
+        }
+    ));
+    app.add_action(&shortcuts);
+
+    let import = gio::SimpleAction::new("import", None);
+    import.connect_activate(clone!(
+        #[weak]
+        window,
+        #[strong]
+        state,
+        #[strong]
+        widgets,
+        #[strong]
+        settings,
+        move |_, _| {
+            choose_import_file(&window, &widgets, &state, &settings);
+        }
+    ));
+    app.add_action(&import);
+    app.set_accels_for_action("app.import", &["<Control>o"]);
+
+    let export = gio::SimpleAction::new("export", None);
+    export.connect_activate(clone!(
+        #[weak]
+        window,
+        #[strong]
+        state,
+        #[strong]
+        widgets,
+        move |_, _| {
+            show_export_dialog(&window, &widgets, &state);
+        }
+    ));
+    app.add_action(&export);
+    app.set_accels_for_action("app.export", &["<Control>e"]);
+
+    let back_up = gio::SimpleAction::new("back-up", None);
+    back_up.connect_activate(clone!(
+        #[weak]
+        window,
+        #[strong]
+        widgets,
+        #[strong]
+        settings,
+        move |_, _| {
+            back_up_queue(&window, &widgets, &settings);
+        }
+    ));
+    app.add_action(&back_up);
+
+    let restore = gio::SimpleAction::new("restore", None);
+    restore.connect_activate(clone!(
+        #[weak]
+        window,
+        #[strong]
+        state,
+        #[strong]
+        widgets,
+        #[strong]
+        settings,
+        move |_, _| {
+            restore_queue(&window, &widgets, &state, &settings);
+        }
+    ));
+    app.add_action(&restore);
+
+    let save_view = gio::SimpleAction::new("save-view", None);
+    save_view.connect_activate(clone!(
+        #[weak]
+        window,
+        #[strong]
+        state,
+        #[strong]
+        widgets,
+        #[strong]
+        settings,
+        move |_, _| {
+            show_save_view_dialog(&window, &state, &widgets, &settings);
+        }
+    ));
+    app.add_action(&save_view);
+    app.set_accels_for_action("app.save-view", &["<Control>s"]);
 
-    page.append(&clamp);
-    page
+    let remove_view = gio::SimpleAction::new("remove-view", Some(glib::VariantTy::STRING));
+    remove_view.connect_activate(clone!(
+        #[strong]
+        state,
+        #[strong]
+        widgets,
+        #[strong]
+        settings,
+        move |_, param| {
+            if let Some(name) = param.and_then(|p| p.get::<String>()) {
+                remove_saved_view(&name, &state, &widgets, &settings);
+            }
+        }
+    ));
+    app.add_action(&remove_view);
 }
 
-// ============================================================================
-// Keyboard Handling
-// ============================================================================
-
-fn handle_key_press(
-    key: gtk::gdk::Key,
-    modifier: gtk::gdk::ModifierType,
+/// Register the actions command-line requests are forwarded to, so a running
+/// instance handles them in its existing window
+fn install_command_actions(
+    app: &adw::Application,
+    window: &adw::ApplicationWindow,
     state: &Rc<RefCell<AppState>>,
     widgets: &Rc<Widgets>,
     settings: &Rc<RefCell<AppSettings>>,
-    window: &adw::ApplicationWindow,
-) -> bool {
-    let ctrl = modifier.contains(gtk::gdk::ModifierType::CONTROL_MASK);
-
-    match key {
-        gtk::gdk::Key::r if ctrl => {
-            trigger_refresh(state, widgets, settings);
-            widgets.show_toast("Refreshing...");
-            true
+) {
+    let add_urls = gio::SimpleAction::new("add-urls", Some(glib::VariantTy::STRING_ARRAY));
+    add_urls.connect_activate(clone!(
+        #[strong]
+        state,
+        #[strong]
+        widgets,
+        #[strong]
+        settings,
+        move |_, param| {
+            let Some(urls) = param.and_then(|p| p.get::<Vec<String>>()) else {
+                return;
+            };
+            if !urls.is_empty() {
+                submit_urls(urls, &widgets, &state, &settings);
+            }
         }
-        gtk::gdk::Key::d if ctrl => {
+    ));
+    app.add_action(&add_urls);
+
+    let start = gio::SimpleAction::new("start-downloads", Some(glib::VariantTy::INT32));
+    start.connect_activate(clone!(
+        #[strong]
+        state,
+        #[strong]
+        widgets,
+        #[strong]
+        settings,
+        move |_, param| {
+            let Some(limit) = param.and_then(|p| p.get::<i32>()) else {
+                return;
+            };
             let api_url = settings.borrow().api_url.clone();
             do_action(
                 &api_url,
-                |url| api::start_downloads(&url, 3),
-                widgets,
-                state,
-                settings,
+                move |url| api::start_downloads(&url, limit),
+                &widgets,
+                &state,
+                &settings,
             );
-            true
         }
-        gtk::gdk::Key::n if ctrl => {
-            show_add_urls_dialog(window, widgets, state, settings);
-            true
+    ));
+    app.add_action(&start);
+
+    let show_page = gio::SimpleAction::new("show-page", Some(glib::VariantTy::STRING));
+    show_page.connect_activate(clone!(
+        #[weak]
+        window,
+        #[strong]
+        widgets,
+        move |_, param| {
+            if let Some(page) = param.and_then(|p| p.get::<String>()) {
+                // A collection or saved view already on that page stays selected
+                if selected_sidebar_item(&widgets).is_none_or(|item| item.page() != page) {
+                    select_sidebar_item(&widgets, &format!("page:{page}"));
+                }
+                widgets.split_view.set_show_content(true);
+            }
+            window.present();
         }
-        gtk::gdk::Key::question => {
-            show_keyboard_help(window);
-            true
+    ));
+    app.add_action(&show_page);
+
+    let open_link = gio::SimpleAction::new("open-link", Some(glib::VariantTy::STRING));
+    open_link.connect_activate(clone!(
+        #[weak]
+        window,
+        #[strong]
+        state,
+        #[strong]
+        widgets,
+        #[strong]
+        settings,
+        move |_, param| {
+            let Some(link) = param.and_then(|p| p.get::<String>()) else {
+                return;
+            };
+            window.present();
+            match scheme::parse_add_link(&link) {
+                Ok(urls) => show_add_confirmation(&window, urls, &widgets, &state, &settings),
+                Err(e) => widgets.show_toast(&e),
+            }
         }
-        _ => false,
+    ));
+    app.add_action(&open_link);
+}
+
+/// Handle an invocation forwarded by `main` (possibly from another process),
+/// opening the window if needed and running its requests there
+fn handle_command_line(
+    app: &adw::Application,
+    cmdline: &gio::ApplicationCommandLine,
+) -> glib::ExitCode {
+    let args: Vec<String> = cmdline
+        .arguments()
+        .iter()
+        .skip(1)
+        .map(|a| a.to_string_lossy().into_owned())
+        .collect();
+    // Arguments were validated before being forwarded
+    let Ok(options) = cli::parse(&args) else {
+        return glib::ExitCode::FAILURE;
+    };
+
+    let running = !app.windows().is_empty();
+    if running {
+        let has_requests =
+            !options.add.is_empty() || options.start.is_some() || !options.links.is_empty();
+        if options.show.is_none() && !has_requests {
+            // A plain launch brings the existing window forward
+            app.activate();
+        }
+    } else {
+        build_ui(app, &options);
+    }
+
+    if !options.add.is_empty() {
+        app.activate_action("add-urls", Some(&options.add.to_variant()));
     }
+    if let Some(limit) = options.start {
+        app.activate_action("start-downloads", Some(&limit.to_variant()));
+    }
+    if let Some(page) = &options.show {
+        app.activate_action("show-page", Some(&page.to_variant()));
+    }
+    for link in &options.links {
+        app.activate_action("open-link", Some(&link.to_variant()));
+    }
+    glib::ExitCode::SUCCESS
 }
 
-// ============================================================================
-// UI Building
-// ============================================================================
+/// Run `--add`/`--start` against the API without a window, for scripts
+fn run_headless(options: &CliOptions) -> glib::ExitCode {
+    let api_url = options
+        .api_url
+        .clone()
+        .unwrap_or_else(|| load_settings().api_url);
+    let mut code = glib::ExitCode::SUCCESS;
+
+    if !options.add.is_empty() {
+        match api::add_urls(&api_url, &options.add) {
+            Ok(msg) => println!("{msg}"),
+            Err(e) => {
+                eprintln!("dlm-gtk: Failed to add URLs: {e}");
+                code = glib::ExitCode::FAILURE;
+            }
+        }
+    }
+    if let Some(limit) = options.start {
+        match api::start_downloads(&api_url, limit) {
+            Ok(msg) => println!("{msg}"),
+            Err(e) => {
+                eprintln!("dlm-gtk: Failed to start downloads: {e}");
+                code = glib::ExitCode::FAILURE;
+            }
+        }
+    }
+    code
+}
+
+fn build_ui(app: &adw::Application, options: &CliOptions) {
+    // Activating a running instance (e.g. launching it again while it runs in
+    // the background) shows the existing window
+    if let Some(window) = app.windows().first() {
+        window.present();
+        return;
+    }
 
-fn build_ui(app: &adw::Application) {
     load_css();
 
     let settings = Rc::new(RefCell::new(load_settings()));
-    let state = Rc::new(RefCell::new(AppState::default()));
+    if let Some(api_url) = &options.api_url {
+        settings.borrow_mut().api_url = api_url.clone();
+    }
+    let state = Rc::new(RefCell::new(AppState {
+        sort_order: settings.borrow().sort_order,
+        group_by: settings.borrow().group_by,
+        ..Default::default()
+    }));
 
     // Create all shared widgets
+    let downloads_store = gio::ListStore::new::<glib::Object>();
+    let downloads_context: Rc<RefCell<RowContext>> = Rc::default();
+    let errors_context: Rc<RefCell<RowContext>> = Rc::default();
+    let downloads_selection = gtk::MultiSelection::new(Some(downloads_store.clone()));
+    let errors_store = gio::ListStore::new::<DownloadObject>();
+    let sort_labels: Vec<&str> = SortKey::ALL.iter().map(|k| k.label()).collect();
+    let group_labels: Vec<&str> = GroupBy::ALL.iter().map(|g| g.label()).collect();
     let widgets = Rc::new(Widgets {
         toast_overlay: adw::ToastOverlay::new(),
+        connection_banner: adw::Banner::builder()
+            .button_label("Reconnect")
+            .revealed(false)
+            .build(),
         pending_label: gtk::Label::new(Some("0")),
         downloading_label: gtk::Label::new(Some("0")),
         success_label: gtk::Label::new(Some("0")),
         error_count_label: gtk::Label::new(Some("0")),
         system_label: gtk::Label::new(Some("")),
-        downloads_list: gtk::ListBox::new(),
+        split_view: adw::NavigationSplitView::new(),
+        content_page: adw::NavigationPage::builder().title("Downloads").build(),
+        view_stack: adw::ViewStack::new(),
+        sidebar_list: gtk::ListBox::builder()
+            .css_classes(["navigation-sidebar"])
+            .build(),
+        sidebar_items: Rc::default(),
+        sidebar_counts: RefCell::default(),
+        status_buttons: StatusFilter::ALL_FILTERS
+            .iter()
+            .map(|&filter| (filter, gtk::ToggleButton::with_label(filter.label())))
+            .collect(),
+        downloads_sort: gtk::DropDown::from_strings(&sort_labels),
+        downloads_sort_direction: gtk::Button::new(),
+        downloads_group: gtk::DropDown::from_strings(&group_labels),
+        downloads_list: gtk::ListView::builder()
+            .model(&downloads_selection)
+            .factory(&build_download_factory(false, downloads_context.clone()))
+            .enable_rubberband(true)
+            .build(),
+        downloads_search_bar: gtk::SearchBar::new(),
+        downloads_search: gtk::SearchEntry::new(),
+        downloads_search_error: gtk::Label::new(None),
+        downloads_context,
+        downloads_store,
+        downloads_selection,
+        downloads_stack: gtk::Stack::new(),
+        downloads_empty: adw::StatusPage::new(),
         downloads_count_label: gtk::Label::new(Some("")),
-        errors_list: gtk::ListBox::new(),
+        selection_bar: gtk::ActionBar::new(),
+        selection_label: gtk::Label::new(Some("")),
+        selection_progress: gtk::ProgressBar::new(),
+        selection_buttons: DownloadAction::ALL
+            .iter()
+            .map(|&action| (action, gtk::Button::with_label(action.label())))
+            .collect(),
+        errors_list: gtk::ListView::new(
+            Some(gtk::NoSelection::new(Some(errors_store.clone()))),
+            Some(build_download_factory(true, errors_context.clone())),
+        ),
+        errors_context,
+        errors_store,
         errors_stack: gtk::Stack::new(),
         logs_buffer: gtk::TextBuffer::new(None),
         logs_search: gtk::SearchEntry::new(),
@@ -1350,38 +4016,13 @@ fn build_ui(app: &adw::Application) {
     let logs_page = build_logs_page(&widgets, &state);
     let config_page = build_config_page(&widgets);
 
-    // View stack
-    let view_stack = adw::ViewStack::new();
-    view_stack.add_titled_with_icon(
-        &downloads_page,
-        Some("downloads"),
-        "Downloads",
-        "folder-download-symbolic",
-    );
-    view_stack.add_titled_with_icon(
-        &errors_page,
-        Some("errors"),
-        "Errors",
-        "dialog-error-symbolic",
-    );
-    view_stack.add_titled_with_icon(
-        &logs_page,
-        Some("logs"),
-        "Logs",
-        "utilities-terminal-symbolic",
-    );
-    view_stack.add_titled_with_icon(
-        &config_page,
-        Some("config"),
-        "Config",
-        "emblem-system-symbolic",
-    );
-
-    // Header bar with view switcher
-    let switcher = adw::ViewSwitcher::builder()
-        .stack(&view_stack)
-        .policy(adw::ViewSwitcherPolicy::Wide)
-        .build();
+    // Pages, navigated from the sidebar
+    let pages = [&downloads_page, &errors_page, &logs_page, &config_page];
+    for (page, (name, title, icon)) in pages.into_iter().zip(PAGES) {
+        widgets
+            .view_stack
+            .add_titled_with_icon(page, Some(name), title, icon);
+    }
 
     let add_btn = gtk::Button::builder()
         .icon_name("list-add-symbolic")
@@ -1398,18 +4039,105 @@ fn build_ui(app: &adw::Application) {
         .tooltip_text("Settings")
         .build();
 
+    let menu = gio::Menu::new();
+    let files_section = gio::Menu::new();
+    files_section.append(Some("_Import URLs…"), Some("app.import"));
+    files_section.append(Some("_Export Downloads…"), Some("app.export"));
+    menu.append_section(None, &files_section);
+    let backup_section = gio::Menu::new();
+    backup_section.append(Some("_Back Up Queue…"), Some("app.back-up"));
+    backup_section.append(Some("_Restore Queue…"), Some("app.restore"));
+    menu.append_section(None, &backup_section);
+    let app_section = gio::Menu::new();
+    app_section.append(Some("_Keyboard Shortcuts"), Some("app.shortcuts"));
+    app_section.append(Some("_Quit"), Some("app.quit"));
+    menu.append_section(None, &app_section);
+    let menu_btn = gtk::MenuButton::builder()
+        .icon_name("open-menu-symbolic")
+        .tooltip_text("Main Menu")
+        .menu_model(&menu)
+        .primary(true)
+        .build();
+
+    // Sidebar: pages, then collections and saved views
+    let sidebar_header = adw::HeaderBar::new();
+    sidebar_header.pack_end(&menu_btn);
+    let sidebar_scroll = gtk::ScrolledWindow::builder()
+        .hscrollbar_policy(gtk::PolicyType::Never)
+        .child(&widgets.sidebar_list)
+        .vexpand(true)
+        .build();
+    let sidebar_view = adw::ToolbarView::new();
+    sidebar_view.add_top_bar(&sidebar_header);
+    sidebar_view.set_content(Some(&sidebar_scroll));
+    widgets
+        .split_view
+        .set_sidebar(Some(&adw::NavigationPage::new(&sidebar_view, WINDOW_TITLE)));
+
+    let items = widgets.sidebar_items.clone();
+    widgets.sidebar_list.set_header_func(move |row, before| {
+        let items = items.borrow();
+        let section = items.get(row.index() as usize).and_then(|i| i.section());
+        let before_section = before
+            .and_then(|b| items.get(b.index() as usize))
+            .and_then(|i| i.section());
+        match section {
+            Some(title) if section != before_section => {
+                let heading = gtk::Label::builder()
+                    .label(title)
+                    .xalign(0.0)
+                    .margin_top(12)
+                    .margin_bottom(6)
+                    .margin_start(12)
+                    .css_classes(["caption-heading", "dim-label"])
+                    .build();
+                row.set_header(Some(&heading));
+            }
+            _ => row.set_header(None::<&gtk::Widget>),
+        }
+    });
+    widgets.sidebar_list.connect_row_selected(clone!(
+        #[strong]
+        state,
+        #[strong]
+        widgets,
+        move |_, row| {
+            let item = row.and_then(|r| {
+                widgets
+                    .sidebar_items
+                    .borrow()
+                    .get(r.index() as usize)
+                    .cloned()
+            });
+            if let Some(item) = item {
+                show_sidebar_item(&item, &state, &widgets);
+            }
+        }
+    ));
+    widgets.sidebar_list.connect_row_activated(clone!(
+        #[strong]
+        widgets,
+        move |_, _| {
+            widgets.split_view.set_show_content(true);
+        }
+    ));
+    let views = settings.borrow().saved_views.clone();
+    update_sidebar(&state, &widgets, &views);
+
+    // Content: the selected page
     let header = adw::HeaderBar::new();
-    header.set_title_widget(Some(&switcher));
     header.pack_start(&add_btn);
     header.pack_end(&settings_btn);
     header.pack_end(&refresh_btn);
 
-    // Assemble main layout
     let toolbar_view = adw::ToolbarView::new();
     toolbar_view.add_top_bar(&header);
-    toolbar_view.set_content(Some(&view_stack));
+    toolbar_view.add_top_bar(&widgets.connection_banner);
+    toolbar_view.set_content(Some(&widgets.view_stack));
+    widgets.content_page.set_child(Some(&toolbar_view));
+    widgets.split_view.set_content(Some(&widgets.content_page));
 
-    widgets.toast_overlay.set_child(Some(&toolbar_view));
+    widgets.toast_overlay.set_child(Some(&widgets.split_view));
 
     let window = adw::ApplicationWindow::builder()
         .application(app)
@@ -1419,6 +4147,82 @@ fn build_ui(app: &adw::Application) {
         .content(&widgets.toast_overlay)
         .build();
 
+    // Collapse the sidebar into a separate view on narrow windows
+    let breakpoint = adw::Breakpoint::new(adw::BreakpointCondition::new_length(
+        adw::BreakpointConditionLengthType::MaxWidth,
+        SIDEBAR_BREAKPOINT_SP,
+        adw::LengthUnit::Sp,
+    ));
+    breakpoint.add_setter(&widgets.split_view, "collapsed", Some(&true.to_value()));
+    window.add_breakpoint(breakpoint);
+
+    install_download_actions(app, &window, &state, &widgets, &settings);
+    install_app_actions(app, &window, &state, &widgets, &settings);
+    install_command_actions(app, &window, &state, &widgets, &settings);
+
+    // Closing hides the window while running in the background, holding the
+    // application so refreshes and notifications continue
+    let hold: Rc<RefCell<Option<gio::ApplicationHoldGuard>>> = Rc::default();
+    window.connect_close_request(clone!(
+        #[strong]
+        settings,
+        move |window| {
+            if settings.borrow().run_in_background {
+                window.set_visible(false);
+                glib::Propagation::Stop
+            } else {
+                glib::Propagation::Proceed
+            }
+        }
+    ));
+    window.connect_visible_notify(clone!(
+        #[strong]
+        state,
+        #[strong]
+        widgets,
+        #[strong]
+        settings,
+        #[strong]
+        hold,
+        move |window| {
+            let hidden = !window.is_visible();
+            state.borrow_mut().background = hidden;
+            if hidden {
+                *hold.borrow_mut() = window.application().map(|app| app.hold());
+            } else {
+                hold.take();
+                // Catch up straight away rather than at the slower rate
+                trigger_refresh(&state, &widgets, &settings);
+            }
+        }
+    ));
+
+    // Activating a row (double-click or Enter) opens its details, or
+    // collapses or expands a group
+    for list in [&widgets.downloads_list, &widgets.errors_list] {
+        list.connect_activate(clone!(
+            #[strong]
+            state,
+            #[strong]
+            widgets,
+            move |list, position| {
+                let Some(item) = list.model().and_then(|m| m.item(position)) else {
+                    return;
+                };
+                if let Some(group) = item.downcast_ref::<GroupObject>() {
+                    let key = group.header().key.clone();
+                    toggle_group_collapsed(&state, &widgets, key);
+                    return;
+                }
+                let Some(obj) = item.downcast_ref::<DownloadObject>() else {
+                    return;
+                };
+                let dl = obj.download().clone();
+                show_download_details(list, &dl, &state.borrow());
+            }
+        ));
+    }
+
     // Connect signals
     add_btn.connect_clicked(clone!(
         #[strong]
@@ -1430,7 +4234,7 @@ fn build_ui(app: &adw::Application) {
         #[strong]
         window,
         move |_| {
-            show_add_urls_dialog(&window, &widgets, &state, &settings);
+            show_add_urls_dialog(&window, &widgets, &state, &settings, &[]);
         }
     ));
 
@@ -1461,6 +4265,74 @@ fn build_ui(app: &adw::Application) {
         }
     ));
 
+    widgets.connection_banner.connect_button_clicked(clone!(
+        #[strong]
+        state,
+        #[strong]
+        widgets,
+        #[strong]
+        settings,
+        move |_| {
+            state.borrow_mut().reset_backoff();
+            trigger_refresh(&state, &widgets, &settings);
+            widgets.show_toast("Reconnecting...");
+        }
+    ));
+
+    // Copied links, checked on change and when the window regains focus
+    window.clipboard().connect_changed(clone!(
+        #[strong]
+        state,
+        #[strong]
+        widgets,
+        #[strong]
+        settings,
+        #[weak]
+        window,
+        move |_| {
+            check_clipboard(&window, &state, &widgets, &settings);
+        }
+    ));
+    window.connect_is_active_notify(clone!(
+        #[strong]
+        state,
+        #[strong]
+        widgets,
+        #[strong]
+        settings,
+        move |window| {
+            if window.is_active() {
+                check_clipboard(window, &state, &widgets, &settings);
+            }
+        }
+    ));
+
+    // Dropping links, text or link files opens the add dialog with their URLs
+    let drop_target = gtk::DropTarget::new(glib::Type::INVALID, gtk::gdk::DragAction::COPY);
+    drop_target.set_types(&[gtk::gdk::FileList::static_type(), glib::Type::STRING]);
+    drop_target.connect_drop(clone!(
+        #[strong]
+        state,
+        #[strong]
+        widgets,
+        #[strong]
+        settings,
+        #[weak(rename_to = window)]
+        window,
+        #[upgrade_or]
+        false,
+        move |_, value, _, _| {
+            let urls = dropped_urls(value);
+            if urls.is_empty() {
+                widgets.show_toast("No http(s) URLs found in the dropped items");
+                return false;
+            }
+            show_add_urls_dialog(&window, &widgets, &state, &settings, &urls);
+            true
+        }
+    ));
+    window.add_controller(drop_target);
+
     // Keyboard handler
     let key_controller = gtk::EventControllerKey::new();
     key_controller.connect_key_pressed(clone!(
@@ -1482,10 +4354,11 @@ fn build_ui(app: &adw::Application) {
     ));
     window.add_controller(key_controller);
 
-    // Initial data load
+    // Initial data load, then live updates where the server supports them
     trigger_refresh(&state, &widgets, &settings);
+    start_event_stream(&state, &widgets, &settings);
 
-    // Auto-refresh timer
+    // Auto-refresh timer, backing off while the server is unreachable
     glib::timeout_add_seconds_local(
         REFRESH_INTERVAL_SECS,
         clone!(
@@ -1496,17 +4369,70 @@ fn build_ui(app: &adw::Application) {
             #[strong]
             settings,
             move || {
-                trigger_refresh(&state, &widgets, &settings);
+                let due = state.borrow_mut().tick();
+                if due {
+                    trigger_refresh(&state, &widgets, &settings);
+                } else {
+                    update_connection_banner(&state.borrow(), &widgets);
+                }
                 glib::ControlFlow::Continue
             }
         ),
     );
 
-    window.present();
+    if options.background {
+        state.borrow_mut().background = true;
+        *hold.borrow_mut() = Some(app.hold());
+    } else {
+        window.present();
+    }
 }
 
 fn main() -> glib::ExitCode {
-    let app = adw::Application::builder().application_id(APP_ID).build();
-    app.connect_activate(build_ui);
-    app.run()
+    let mut args: Vec<String> = std::env::args().collect();
+    let mut options = match cli::parse(&args[1..]) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("dlm-gtk: {e}\n\n{}", cli::USAGE);
+            return glib::ExitCode::from(2);
+        }
+    };
+    if options.help {
+        println!("{}", cli::USAGE);
+        return glib::ExitCode::SUCCESS;
+    }
+
+    // Stdin isn't forwarded to the primary instance, so read it here
+    if options.reads_stdin() {
+        let mut text = String::new();
+        if let Err(e) = std::io::Read::read_to_string(&mut std::io::stdin(), &mut text) {
+            eprintln!("dlm-gtk: Failed to read URLs from stdin: {e}");
+            return glib::ExitCode::FAILURE;
+        }
+        options.add.retain(|u| u != cli::STDIN_ARG);
+        options.add.extend(types::parse_url_list(&text));
+        if options.add.is_empty() && options.start.is_none() && options.show.is_none() {
+            eprintln!("dlm-gtk: No URLs on stdin");
+            return glib::ExitCode::FAILURE;
+        }
+    }
+    args.truncate(1);
+    args.extend(options.to_args());
+
+    let app = adw::Application::builder()
+        .application_id(APP_ID)
+        .flags(gio::ApplicationFlags::HANDLES_COMMAND_LINE)
+        .build();
+
+    // Nothing to show and no instance to forward to: just make the calls
+    if options.is_headless() {
+        let registered = app.register(gio::Cancellable::NONE).is_ok();
+        if !registered || !app.is_remote() {
+            return run_headless(&options);
+        }
+    }
+
+    app.connect_activate(|app| build_ui(app, &CliOptions::default()));
+    app.connect_command_line(handle_command_line);
+    app.run_with_args(&args)
 }
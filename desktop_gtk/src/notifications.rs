@@ -0,0 +1,225 @@
+//! Desktop notifications for downloads that finish or fail.
+//!
+//! Successive snapshots of the download list are compared by id, and each
+//! download that moved to `success` or `error` becomes a notification. Bursts
+//! (e.g. a whole batch finishing between two refreshes) are coalesced into a
+//! single summary so the desktop isn't flooded.
+
+use std::collections::HashMap;
+
+use gtk::gio;
+use gtk::prelude::*;
+
+use crate::config::AppSettings;
+use crate::types::{Download, DownloadAction, DownloadStatus};
+
+/// More transitions than this at once are sent as one summary
+const MAX_INDIVIDUAL: usize = 3;
+/// Longest error excerpt shown in a notification body
+const MAX_EXCERPT_CHARS: usize = 120;
+/// Notification id for the coalesced summary, so a new burst replaces it
+const SUMMARY_ID: &str = "downloads-summary";
+
+/// A notification ready to send
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Notice {
+    /// Notification id; sending again with the same id replaces it
+    pub id: String,
+    pub title: String,
+    pub body: String,
+    /// The download a single-item notice is about, for its buttons
+    pub download: Option<Download>,
+}
+
+/// Downloads in `after` whose status changed to `success` or `error` since
+/// `before`. Downloads not present in `before` are skipped, so the first load
+/// doesn't announce the whole history.
+pub fn transitions<'a>(before: &[Download], after: &'a [Download]) -> Vec<&'a Download> {
+    let previous: HashMap<i64, &DownloadStatus> =
+        before.iter().map(|d| (d.id, &d.status)).collect();
+    after
+        .iter()
+        .filter(|d| matches!(d.status, DownloadStatus::Success | DownloadStatus::Error))
+        .filter(|d| previous.get(&d.id).is_some_and(|s| **s != d.status))
+        .collect()
+}
+
+/// Turn transitions into notices, keeping only the kinds enabled in settings
+pub fn plan(transitions: &[&Download], settings: &AppSettings) -> Vec<Notice> {
+    let wanted: Vec<&Download> = transitions
+        .iter()
+        .copied()
+        .filter(|d| match d.status {
+            DownloadStatus::Success => settings.notify_success,
+            DownloadStatus::Error => settings.notify_errors,
+            _ => false,
+        })
+        .collect();
+
+    if wanted.len() > MAX_INDIVIDUAL {
+        return vec![summary(&wanted)];
+    }
+    wanted.into_iter().map(single).collect()
+}
+
+fn single(dl: &Download) -> Notice {
+    let failed = dl.status == DownloadStatus::Error;
+    let mut body = format!("{} · {}", dl.display_title(), dl.collection);
+    if failed && let Some(msg) = &dl.error_message {
+        body.push('\n');
+        body.push_str(&excerpt(msg));
+    }
+    Notice {
+        id: format!("download-{}", dl.id),
+        title: if failed {
+            "Download failed".into()
+        } else {
+            "Download finished".into()
+        },
+        body,
+        download: Some(dl.clone()),
+    }
+}
+
+fn summary(downloads: &[&Download]) -> Notice {
+    let failed = downloads
+        .iter()
+        .filter(|d| d.status == DownloadStatus::Error)
+        .count();
+    let finished = downloads.len() - failed;
+    let title = match (finished, failed) {
+        (_, 0) => format!("{finished} downloads finished"),
+        (0, _) => format!("{failed} downloads failed"),
+        _ => format!("{finished} downloads finished, {failed} failed"),
+    };
+    let mut names: Vec<&str> = downloads
+        .iter()
+        .take(MAX_INDIVIDUAL)
+        .map(|d| d.display_title())
+        .collect();
+    if downloads.len() > MAX_INDIVIDUAL {
+        names.push("…");
+    }
+    Notice {
+        id: SUMMARY_ID.into(),
+        title,
+        body: names.join(", "),
+        download: None,
+    }
+}
+
+/// First line of an error message, shortened for a notification body
+fn excerpt(msg: &str) -> String {
+    let line = msg.trim().lines().next().unwrap_or_default();
+    if line.chars().count() > MAX_EXCERPT_CHARS {
+        let cut: String = line.chars().take(MAX_EXCERPT_CHARS).collect();
+        format!("{}…", cut.trim_end())
+    } else {
+        line.to_string()
+    }
+}
+
+/// Send notices through the running application. Buttons use the same
+/// `app.*` actions as the download rows.
+pub fn send(notices: Vec<Notice>) {
+    let Some(app) = gio::Application::default() else {
+        return;
+    };
+    for notice in notices {
+        let notification = gio::Notification::new(&notice.title);
+        notification.set_body(Some(&notice.body));
+        if let Some(dl) = &notice.download {
+            notification.add_button_with_target_value(
+                "Open Folder",
+                "app.open-folder",
+                Some(&dl.collection.to_variant()),
+            );
+            if dl.status == DownloadStatus::Error {
+                notification.add_button_with_target_value(
+                    DownloadAction::Retry.label(),
+                    &format!("app.{}", DownloadAction::Retry.name()),
+                    Some(&dl.id.to_variant()),
+                );
+            }
+        }
+        app.send_notification(Some(&notice.id), &notification);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::tests::download;
+
+    fn dl(id: i64, status: &str) -> Download {
+        Download {
+            collection: "yt".into(),
+            title: Some(format!("Video {id}")),
+            ..download(id, status)
+        }
+    }
+
+    #[test]
+    fn test_transitions() {
+        let before = vec![dl(1, "downloading"), dl(2, "pending"), dl(3, "success")];
+        let after = vec![
+            dl(1, "success"),
+            dl(2, "error"),
+            dl(3, "success"),
+            // New and already finished: not announced
+            dl(4, "success"),
+        ];
+        let ids: Vec<i64> = transitions(&before, &after).iter().map(|d| d.id).collect();
+        assert_eq!(ids, vec![1, 2]);
+
+        assert!(transitions(&[], &after).is_empty());
+    }
+
+    #[test]
+    fn test_plan_single_notices_respect_settings() {
+        let mut failed = dl(2, "error");
+        failed.error_message = Some("ERROR: Unsupported URL\nmore details".into());
+        let finished = dl(1, "success");
+        let settings = AppSettings::default();
+
+        let notices = plan(&[&finished, &failed], &settings);
+        assert_eq!(notices.len(), 2);
+        assert_eq!(notices[0].id, "download-1");
+        assert_eq!(notices[0].title, "Download finished");
+        assert_eq!(notices[0].body, "Video 1 · yt");
+        assert_eq!(notices[1].title, "Download failed");
+        assert_eq!(notices[1].body, "Video 2 · yt\nERROR: Unsupported URL");
+
+        let errors_only = AppSettings {
+            notify_success: false,
+            ..AppSettings::default()
+        };
+        let notices = plan(&[&finished, &failed], &errors_only);
+        assert_eq!(notices.len(), 1);
+        assert_eq!(notices[0].id, "download-2");
+    }
+
+    #[test]
+    fn test_plan_coalesces_bursts() {
+        let downloads: Vec<Download> = (1..=5)
+            .map(|id| dl(id, if id == 5 { "error" } else { "success" }))
+            .collect();
+        let refs: Vec<&Download> = downloads.iter().collect();
+
+        let notices = plan(&refs, &AppSettings::default());
+        assert_eq!(notices.len(), 1);
+        assert_eq!(notices[0].id, SUMMARY_ID);
+        assert_eq!(notices[0].title, "4 downloads finished, 1 failed");
+        assert_eq!(notices[0].body, "Video 1, Video 2, Video 3, …");
+        assert!(notices[0].download.is_none());
+    }
+
+    #[test]
+    fn test_excerpt() {
+        assert_eq!(excerpt("  first line\nsecond"), "first line");
+        let long = "x".repeat(200);
+        let short = excerpt(&long);
+        assert_eq!(short.chars().count(), MAX_EXCERPT_CHARS + 1);
+        assert!(short.ends_with('…'));
+    }
+}
@@ -0,0 +1,94 @@
+//! `dlm://` links, for queueing downloads from notes, chat or bookmarklets:
+//!
+//! ```text
+//! dlm://add?url=https%3A%2F%2Fyoutube.com%2Fwatch%3Fv%3Dabc&url=https%3A%2F%2F...
+//! ```
+//!
+//! Each `url` parameter is one URL to add, percent-encoded. The desktop entry
+//! registers the app for `x-scheme-handler/dlm`, so opening a link launches
+//! `dlm-gtk dlm://...`.
+
+use gtk::glib;
+
+pub const SCHEME: &str = "dlm";
+
+/// Whether a command-line argument is a `dlm:` link
+pub fn is_link(arg: &str) -> bool {
+    arg.split_once(':')
+        .is_some_and(|(scheme, _)| scheme.eq_ignore_ascii_case(SCHEME))
+}
+
+/// The URLs queued by a `dlm://add` link
+pub fn parse_add_link(link: &str) -> Result<Vec<String>, String> {
+    // Keep the query encoded so `&` inside a URL doesn't split it
+    let uri = glib::Uri::parse(link, glib::UriFlags::ENCODED_QUERY)
+        .map_err(|e| format!("Invalid link: {e}"))?;
+    if !uri.scheme().eq_ignore_ascii_case(SCHEME) {
+        return Err(format!("Not a {SCHEME}:// link"));
+    }
+
+    // `dlm://add?...` puts the action in the host, `dlm:add?...` in the path
+    let action = match uri.host() {
+        Some(host) if !host.is_empty() => host.to_string(),
+        _ => uri.path().trim_matches('/').to_string(),
+    };
+    if action != "add" {
+        return Err(format!("Unsupported {SCHEME}:// link action '{action}'"));
+    }
+
+    let query = uri.query().unwrap_or_default();
+    let urls: Vec<String> = query
+        .split('&')
+        .filter_map(|param| param.split_once('='))
+        .filter(|(key, _)| *key == "url")
+        .filter_map(|(_, value)| glib::Uri::unescape_string(value, None))
+        .map(|url| url.trim().to_string())
+        .filter(|url| !url.is_empty())
+        .collect();
+
+    if urls.is_empty() {
+        return Err("Link has no URLs to add".into());
+    }
+    Ok(urls)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_link() {
+        assert!(is_link("dlm://add?url=x"));
+        assert!(is_link("DLM:add?url=x"));
+        assert!(!is_link("https://example.com"));
+        assert!(!is_link("--add"));
+    }
+
+    #[test]
+    fn test_parse_add_link() {
+        let urls = parse_add_link(
+            "dlm://add?url=https%3A%2F%2Fyoutube.com%2Fwatch%3Fv%3Dabc%26t%3D5\
+             &url=https://example.com/a.jpg&other=1",
+        )
+        .unwrap();
+        assert_eq!(
+            urls,
+            vec![
+                "https://youtube.com/watch?v=abc&t=5",
+                "https://example.com/a.jpg"
+            ]
+        );
+
+        let urls = parse_add_link("dlm:add?url=https%3A%2F%2Fexample.com%2Fb").unwrap();
+        assert_eq!(urls, vec!["https://example.com/b"]);
+    }
+
+    #[test]
+    fn test_parse_add_link_errors() {
+        assert!(parse_add_link("dlm://add").is_err());
+        assert!(parse_add_link("dlm://add?url=").is_err());
+        assert!(parse_add_link("dlm://delete?url=https%3A%2F%2Fexample.com").is_err());
+        assert!(parse_add_link("https://example.com/?url=x").is_err());
+        assert!(parse_add_link("not a link").is_err());
+    }
+}
@@ -0,0 +1,68 @@
+//! Highlighting search matches in download rows. The query itself is parsed
+//! into a [`Filter`](crate::types::Filter).
+
+use gtk::glib;
+
+/// Pango attributes for highlighted matches: a translucent yellow that reads
+/// on light and dark backgrounds
+const HIGHLIGHT_ATTRS: &str = r##"bgcolor="#f6d32d" bgalpha="45%""##;
+
+/// Pango markup for `text`, with every case-insensitive match of the terms
+/// highlighted
+pub fn highlight(text: &str, terms: &[String]) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut marked = vec![false; chars.len()];
+    for term in terms {
+        let term: Vec<char> = term.chars().collect();
+        if term.is_empty() || term.len() > chars.len() {
+            continue;
+        }
+        for start in 0..=chars.len() - term.len() {
+            let hit = term
+                .iter()
+                .zip(&chars[start..])
+                .all(|(t, c)| c.to_lowercase().eq(t.to_lowercase()));
+            if hit {
+                marked[start..start + term.len()].fill(true);
+            }
+        }
+    }
+
+    let mut markup = String::new();
+    let mut start = 0;
+    while start < chars.len() {
+        let is_match = marked[start];
+        let end = (start..chars.len())
+            .find(|&i| marked[i] != is_match)
+            .unwrap_or(chars.len());
+        let segment: String = chars[start..end].iter().collect();
+        let escaped = glib::markup_escape_text(&segment);
+        if is_match {
+            markup.push_str(&format!("<span {HIGHLIGHT_ATTRS}>{escaped}</span>"));
+        } else {
+            markup.push_str(&escaped);
+        }
+        start = end;
+    }
+    markup
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_highlight() {
+        assert_eq!(highlight("a <b> & c", &[]), "a &lt;b&gt; &amp; c");
+        assert_eq!(
+            highlight("Rust talk", &["rust".to_string()]),
+            format!("<span {HIGHLIGHT_ATTRS}>Rust</span> talk")
+        );
+        // Overlapping and adjacent matches merge into one span
+        assert_eq!(
+            highlight("abcd", &["ab".to_string(), "bc".to_string()]),
+            format!("<span {HIGHLIGHT_ATTRS}>abc</span>d")
+        );
+        assert_eq!(highlight("ab", &["abc".to_string()]), "ab");
+    }
+}
@@ -0,0 +1,262 @@
+//! Timestamps from the server, and relative times and durations for display.
+
+use gtk::glib;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+const MINUTE_MS: i64 = 60 * 1000;
+const HOUR_MS: i64 = 60 * MINUTE_MS;
+const DAY_MS: i64 = 24 * HOUR_MS;
+
+/// Relative times switch to the date beyond this age
+const MAX_RELATIVE_MS: i64 = 7 * DAY_MS;
+
+/// A point in time, in milliseconds since the Unix epoch
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Timestamp(pub i64);
+
+impl Timestamp {
+    pub fn now() -> Self {
+        Timestamp(glib::real_time() / 1000)
+    }
+
+    /// Parse an ISO 8601 date-time as the server writes them
+    /// (`2026-10-01T12:00:00.000Z`). Times without an offset are taken as
+    /// UTC, a space may separate date and time, and a bare date is midnight.
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim();
+        let utc = glib::TimeZone::utc();
+        let parsed = glib::DateTime::from_iso8601(text, Some(&utc))
+            .or_else(|_| glib::DateTime::from_iso8601(&format!("{text}T00:00:00"), Some(&utc)))
+            .ok()?;
+        Some(Timestamp(
+            parsed.to_unix() * 1000 + i64::from(parsed.microsecond() / 1000),
+        ))
+    }
+
+    /// The server's form, `2026-10-01T12:00:00.000Z`
+    pub fn to_iso(self) -> String {
+        self.format_utc("%Y-%m-%dT%H:%M:%S")
+            .map(|t| format!("{t}.{:03}Z", self.0.rem_euclid(1000)))
+            .unwrap_or_default()
+    }
+
+    /// The local date, `2026-10-01`
+    pub fn local_date(self) -> String {
+        glib::DateTime::from_unix_local(self.0.div_euclid(1000))
+            .and_then(|t| t.format("%Y-%m-%d"))
+            .map(|t| t.to_string())
+            .unwrap_or_default()
+    }
+
+    /// Local date and time for display, `2026-10-01 14:00:00`
+    pub fn local(self) -> String {
+        glib::DateTime::from_unix_local(self.0.div_euclid(1000))
+            .and_then(|t| t.format("%Y-%m-%d %H:%M:%S"))
+            .map(|t| t.to_string())
+            .unwrap_or_default()
+    }
+
+    /// Rounded down to the minute
+    pub fn to_minute(self) -> Self {
+        Timestamp(self.0 - self.0.rem_euclid(MINUTE_MS))
+    }
+
+    fn format_utc(self, format: &str) -> Option<String> {
+        glib::DateTime::from_unix_utc(self.0.div_euclid(1000))
+            .and_then(|t| t.format(format))
+            .ok()
+            .map(|t| t.to_string())
+    }
+}
+
+impl Serialize for Timestamp {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_iso())
+    }
+}
+
+impl<'de> Deserialize<'de> for Timestamp {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        Timestamp::parse(&text)
+            .ok_or_else(|| serde::de::Error::custom(format!("invalid timestamp '{text}'")))
+    }
+}
+
+/// Deserialize an optional timestamp leniently: `null`, a missing field, or
+/// anything that isn't a parseable date (the server writes `null` for an
+/// invalid `Date`) becomes `None` instead of failing the whole record
+pub fn deserialize_lenient<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<Timestamp>, D::Error> {
+    let value = Option::<serde_json::Value>::deserialize(deserializer)?;
+    Ok(value
+        .as_ref()
+        .and_then(serde_json::Value::as_str)
+        .and_then(Timestamp::parse))
+}
+
+/// Local midnight starting the day a date filter names: an ISO date
+/// (`2026-10-01`), `today`, `yesterday`, or a number of days or weeks back
+/// from `now` (`-7d`, `-2w`)
+pub fn day_start(value: &str, now: Timestamp) -> Option<Timestamp> {
+    let day = if is_iso_date(value) {
+        glib::DateTime::from_iso8601(&format!("{value}T00:00:00"), Some(&glib::TimeZone::local()))
+            .ok()?
+    } else {
+        let days_back = match value {
+            "today" => 0,
+            "yesterday" => 1,
+            _ => {
+                let back = value.strip_prefix('-')?;
+                let (count, unit) = back.split_at(back.len().checked_sub(1)?);
+                let count: i32 = count.parse().ok()?;
+                match unit {
+                    "d" => count,
+                    "w" => count.checked_mul(7)?,
+                    _ => return None,
+                }
+            }
+        };
+        glib::DateTime::from_unix_local(now.0.div_euclid(1000))
+            .ok()?
+            .add_days(-days_back)
+            .ok()?
+    };
+    let midnight =
+        glib::DateTime::from_local(day.year(), day.month(), day.day_of_month(), 0, 0, 0.0).ok()?;
+    Some(Timestamp(midnight.to_unix() * 1000))
+}
+
+fn is_iso_date(value: &str) -> bool {
+    let bytes = value.as_bytes();
+    bytes.len() == 10
+        && bytes.iter().enumerate().all(|(i, b)| match i {
+            4 | 7 => *b == b'-',
+            _ => b.is_ascii_digit(),
+        })
+}
+
+/// How long before `now` something happened: "just now", "5m ago",
+/// "3h ago", "2d ago", or the local date once it's over a week old
+pub fn relative(then: Timestamp, now: Timestamp) -> String {
+    let age = now.0 - then.0;
+    if age < MINUTE_MS {
+        "just now".into()
+    } else if age < HOUR_MS {
+        format!("{}m ago", age / MINUTE_MS)
+    } else if age < DAY_MS {
+        format!("{}h ago", age / HOUR_MS)
+    } else if age < MAX_RELATIVE_MS {
+        format!("{}d ago", age / DAY_MS)
+    } else {
+        let local = then.local();
+        format!("on {}", local.split(' ').next().unwrap_or_default())
+    }
+}
+
+/// A length of time in its two largest units: "45s", "12m", "1h 5m", "2d 3h"
+pub fn duration(ms: i64) -> String {
+    let ms = ms.max(0);
+    if ms < MINUTE_MS {
+        format!("{}s", ms / 1000)
+    } else if ms < HOUR_MS {
+        format!("{}m", ms / MINUTE_MS)
+    } else if ms < DAY_MS {
+        format!("{}h {}m", ms / HOUR_MS, ms % HOUR_MS / MINUTE_MS)
+    } else {
+        format!("{}d {}h", ms / DAY_MS, ms % DAY_MS / HOUR_MS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A local wall-clock time, so tests hold in every time zone
+    fn local(y: i32, m: i32, d: i32, h: i32, min: i32) -> Timestamp {
+        let t = glib::DateTime::from_local(y, m, d, h, min, 0.0).unwrap();
+        Timestamp(t.to_unix() * 1000)
+    }
+
+    #[test]
+    fn test_parse() {
+        let expected = Timestamp(1_790_856_000_123);
+        assert_eq!(Timestamp::parse("2026-10-01T12:00:00.123Z"), Some(expected));
+        assert_eq!(
+            Timestamp::parse("2026-10-01T14:00:00.123+02:00"),
+            Some(expected)
+        );
+        // No offset means UTC; a space may separate date and time
+        assert_eq!(Timestamp::parse("2026-10-01 12:00:00.123"), Some(expected));
+        assert_eq!(
+            Timestamp::parse("2026-10-01"),
+            Some(Timestamp(1_790_812_800_000))
+        );
+        assert_eq!(Timestamp::parse(""), None);
+        assert_eq!(Timestamp::parse("yesterday"), None);
+    }
+
+    #[test]
+    fn test_iso_roundtrip() {
+        let t = Timestamp::parse("2026-10-01T12:00:00.123Z").unwrap();
+        assert_eq!(t.to_iso(), "2026-10-01T12:00:00.123Z");
+        let json = serde_json::to_string(&t).unwrap();
+        assert_eq!(json, "\"2026-10-01T12:00:00.123Z\"");
+        assert_eq!(serde_json::from_str::<Timestamp>(&json).unwrap(), t);
+        assert!(serde_json::from_str::<Timestamp>("\"soon\"").is_err());
+        assert_eq!(t.to_minute().to_iso(), "2026-10-01T12:00:00.000Z");
+    }
+
+    #[test]
+    fn test_local_date() {
+        assert_eq!(local(2026, 10, 1, 0, 0).local_date(), "2026-10-01");
+        assert_eq!(local(2026, 10, 1, 23, 59).local_date(), "2026-10-01");
+    }
+
+    #[test]
+    fn test_day_start() {
+        let midnight = |y, m, d| Some(local(y, m, d, 0, 0));
+        let now = local(2026, 10, 8, 15, 30);
+        assert_eq!(day_start("2026-10-01", now), midnight(2026, 10, 1));
+        assert_eq!(day_start("today", now), midnight(2026, 10, 8));
+        assert_eq!(day_start("yesterday", now), midnight(2026, 10, 7));
+        assert_eq!(day_start("-7d", now), midnight(2026, 10, 1));
+        assert_eq!(day_start("-2w", now), midnight(2026, 9, 24));
+        assert_eq!(day_start("-0d", now), day_start("today", now));
+        for bad in [
+            "",
+            "-",
+            "-d",
+            "7d",
+            "-7x",
+            "-1.5w",
+            "tomorrow",
+            "2026-1-01",
+            "2026-13-01",
+        ] {
+            assert_eq!(day_start(bad, now), None, "{bad}");
+        }
+    }
+
+    #[test]
+    fn test_relative() {
+        let now = Timestamp(1_790_856_000_000);
+        let ago = |ms: i64| relative(Timestamp(now.0 - ms), now);
+        assert_eq!(ago(30 * 1000), "just now");
+        assert_eq!(ago(-5 * MINUTE_MS), "just now");
+        assert_eq!(ago(2 * MINUTE_MS), "2m ago");
+        assert_eq!(ago(3 * HOUR_MS + 59 * MINUTE_MS), "3h ago");
+        assert_eq!(ago(2 * DAY_MS), "2d ago");
+        assert!(ago(30 * DAY_MS).starts_with("on 2026-0"));
+    }
+
+    #[test]
+    fn test_duration() {
+        assert_eq!(duration(45 * 1000), "45s");
+        assert_eq!(duration(12 * MINUTE_MS + 30 * 1000), "12m");
+        assert_eq!(duration(HOUR_MS + 5 * MINUTE_MS), "1h 5m");
+        assert_eq!(duration(2 * DAY_MS + 3 * HOUR_MS), "2d 3h");
+        assert_eq!(duration(-1), "0s");
+    }
+}
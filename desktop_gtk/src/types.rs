@@ -1,17 +1,36 @@
-use serde::Deserialize;
-use std::collections::HashMap;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::marker::PhantomData;
+use std::rc::Rc;
+use std::time::SystemTime;
+
+use crate::api::ApiError;
+use crate::download_row::RowContext;
+use crate::events::ServerEvent;
+use crate::log_entry::{self, LogEntry, LogLevel};
+use crate::time::{self, Timestamp};
 
 /// A download entry from the DLM server
-#[derive(Debug, Default, Clone, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Default, Clone, Deserialize, Serialize, PartialEq, Eq)]
 pub struct Download {
     pub id: i64,
     pub collection: String,
-    #[serde(rename = "createdAt")]
-    pub created_at: String,
-    #[serde(rename = "downloadedAt")]
-    pub downloaded_at: Option<String>,
-    pub priority: String,
-    pub status: String,
+    /// `None` if the server's value is missing or unreadable
+    #[serde(
+        rename = "createdAt",
+        default,
+        deserialize_with = "time::deserialize_lenient"
+    )]
+    pub created_at: Option<Timestamp>,
+    #[serde(
+        rename = "downloadedAt",
+        default,
+        deserialize_with = "time::deserialize_lenient"
+    )]
+    pub downloaded_at: Option<Timestamp>,
+    pub priority: Priority,
+    pub status: DownloadStatus,
     pub title: Option<String>,
     pub url: String,
     #[serde(rename = "errorMessage")]
@@ -23,12 +42,247 @@ impl Download {
     pub fn display_title(&self) -> &str {
         self.title.as_deref().unwrap_or("Untitled")
     }
+
+    /// Milliseconds from being queued to finishing, for successful downloads
+    pub fn time_in_queue(&self) -> Option<i64> {
+        match (&self.status, self.created_at, self.downloaded_at) {
+            (DownloadStatus::Success, Some(created), Some(done)) => Some(done.0 - created.0),
+            _ => None,
+        }
+    }
+}
+
+/// Processing state of a download. Statuses this client doesn't know yet are
+/// kept as `Unknown` rather than failing to load.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum DownloadStatus {
+    #[default]
+    Pending,
+    Downloading,
+    Success,
+    Error,
+    Unknown(String),
+}
+
+impl DownloadStatus {
+    /// Statuses the server reports
+    pub const KNOWN: &[DownloadStatus] = &[
+        DownloadStatus::Pending,
+        DownloadStatus::Downloading,
+        DownloadStatus::Success,
+        DownloadStatus::Error,
+    ];
+
+    /// The server's name for the status
+    pub fn name(&self) -> &str {
+        match self {
+            DownloadStatus::Pending => "pending",
+            DownloadStatus::Downloading => "downloading",
+            DownloadStatus::Success => "success",
+            DownloadStatus::Error => "error",
+            DownloadStatus::Unknown(name) => name,
+        }
+    }
+
+    /// CSS class for the status badge
+    pub fn css_class(&self) -> &'static str {
+        match self {
+            DownloadStatus::Pending => "accent",
+            DownloadStatus::Downloading => "warning",
+            DownloadStatus::Success => "success",
+            DownloadStatus::Error => "error",
+            DownloadStatus::Unknown(_) => "dim-label",
+        }
+    }
+}
+
+impl From<&str> for DownloadStatus {
+    fn from(name: &str) -> Self {
+        match name {
+            "pending" => DownloadStatus::Pending,
+            "downloading" => DownloadStatus::Downloading,
+            "success" => DownloadStatus::Success,
+            "error" => DownloadStatus::Error,
+            other => DownloadStatus::Unknown(other.to_string()),
+        }
+    }
+}
+
+impl From<String> for DownloadStatus {
+    fn from(name: String) -> Self {
+        name.as_str().into()
+    }
+}
+
+impl From<DownloadStatus> for String {
+    fn from(status: DownloadStatus) -> Self {
+        status.name().to_string()
+    }
+}
+
+impl std::fmt::Display for DownloadStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Download priority
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum Priority {
+    #[default]
+    Normal,
+    High,
+    Unknown(String),
+}
+
+impl Priority {
+    /// The server's name for the priority
+    pub fn name(&self) -> &str {
+        match self {
+            Priority::Normal => "normal",
+            Priority::High => "high",
+            Priority::Unknown(name) => name,
+        }
+    }
+}
+
+impl From<&str> for Priority {
+    fn from(name: &str) -> Self {
+        match name {
+            "normal" => Priority::Normal,
+            "high" => Priority::High,
+            other => Priority::Unknown(other.to_string()),
+        }
+    }
+}
+
+impl From<String> for Priority {
+    fn from(name: String) -> Self {
+        name.as_str().into()
+    }
+}
+
+impl From<Priority> for String {
+    fn from(priority: Priority) -> Self {
+        priority.name().to_string()
+    }
+}
+
+impl std::fmt::Display for Priority {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Split pasted or piped text into URLs, one per line or comma-separated
+pub fn parse_url_list(text: &str) -> Vec<String> {
+    text.split('\n')
+        .flat_map(|l| l.split(','))
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+/// Whether `url` is an absolute http(s) URL the server can download
+pub fn is_web_url(url: &str) -> bool {
+    gtk::glib::Uri::parse(url, gtk::glib::UriFlags::NONE).is_ok_and(|uri| {
+        matches!(uri.scheme().to_ascii_lowercase().as_str(), "http" | "https")
+            && uri.host().is_some_and(|h| !h.is_empty())
+    })
+}
+
+/// Actions that can be taken on a single download
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DownloadAction {
+    Retry,
+    Delete,
+    Redownload,
+    Reset,
+}
+
+impl DownloadAction {
+    /// The action offered on a download's row for its status
+    pub fn for_status(status: &DownloadStatus) -> Option<Self> {
+        match status {
+            DownloadStatus::Error => Some(DownloadAction::Retry),
+            DownloadStatus::Pending => Some(DownloadAction::Delete),
+            DownloadStatus::Success => Some(DownloadAction::Redownload),
+            DownloadStatus::Downloading => Some(DownloadAction::Reset),
+            DownloadStatus::Unknown(_) => None,
+        }
+    }
+
+    /// GAction name, registered on the application as `app.<name>`
+    pub fn name(&self) -> &'static str {
+        match self {
+            DownloadAction::Retry => "retry",
+            DownloadAction::Delete => "delete",
+            DownloadAction::Redownload => "redownload",
+            DownloadAction::Reset => "reset",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            DownloadAction::Retry => "Retry",
+            DownloadAction::Delete => "Delete",
+            DownloadAction::Redownload => "Redownload",
+            DownloadAction::Reset => "Reset to pending",
+        }
+    }
+
+    /// Whether a bulk run of this action should include a download in the
+    /// given status
+    pub fn applies_to(&self, status: &DownloadStatus) -> bool {
+        match self {
+            DownloadAction::Retry => *status == DownloadStatus::Error,
+            DownloadAction::Redownload => *status == DownloadStatus::Success,
+            DownloadAction::Reset => *status == DownloadStatus::Downloading,
+            DownloadAction::Delete => *status != DownloadStatus::Downloading,
+        }
+    }
+
+    /// One-line summary of a bulk run, listing the first few failures
+    pub fn summary(&self, total: usize, failures: &[(i64, String)]) -> String {
+        const MAX_LISTED: usize = 3;
+        let succeeded = total - failures.len();
+        let mut text = format!("{}: {succeeded} of {total} succeeded", self.label());
+        if !failures.is_empty() {
+            let listed: Vec<String> = failures
+                .iter()
+                .take(MAX_LISTED)
+                .map(|(id, err)| format!("#{id} ({err})"))
+                .collect();
+            text.push_str(&format!(" · failed: {}", listed.join(", ")));
+            if failures.len() > MAX_LISTED {
+                text.push_str(&format!(" and {} more", failures.len() - MAX_LISTED));
+            }
+        }
+        text
+    }
+
+    pub fn icon_name(&self) -> &'static str {
+        match self {
+            DownloadAction::Retry | DownloadAction::Redownload => "view-refresh-symbolic",
+            DownloadAction::Delete => "user-trash-symbolic",
+            DownloadAction::Reset => "media-playback-stop-symbolic",
+        }
+    }
+
+    pub const ALL: &[DownloadAction] = &[
+        DownloadAction::Retry,
+        DownloadAction::Delete,
+        DownloadAction::Redownload,
+        DownloadAction::Reset,
+    ];
 }
 
 /// Status count from /api/count
 #[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
 pub struct StatusCount {
-    pub status: String,
+    pub status: DownloadStatus,
     pub count: i64,
 }
 
@@ -72,10 +326,67 @@ pub struct CollectionConfig {
     pub domains: Vec<String>,
 }
 
+impl CollectionConfig {
+    /// The command the server runs for `url`, which replaces the first `%`
+    pub fn command_for(&self, url: &str) -> String {
+        self.command.replacen('%', url, 1)
+    }
+}
+
 /// Response from /api/config
 #[derive(Debug, Clone, Deserialize)]
 pub struct ConfigResponse {
-    pub collections: HashMap<String, CollectionConfig>,
+    /// Collections by name, in config order, which decides URL routing
+    #[serde(deserialize_with = "ordered_map")]
+    pub collections: Vec<(String, CollectionConfig)>,
+}
+
+impl ConfigResponse {
+    pub fn collection(&self, name: &str) -> Option<&CollectionConfig> {
+        self.collections
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, c)| c)
+    }
+
+    /// The collection the server would file `url` under: the first one with
+    /// a domain contained in the URL
+    pub fn route(&self, url: &str) -> Option<&str> {
+        self.collections
+            .iter()
+            .find(|(_, c)| c.domains.iter().any(|d| url.contains(d.as_str())))
+            .map(|(name, _)| name.as_str())
+    }
+}
+
+/// Deserialize a JSON object into its entries, keeping their order
+fn ordered_map<'de, D, V>(deserializer: D) -> Result<Vec<(String, V)>, D::Error>
+where
+    D: Deserializer<'de>,
+    V: Deserialize<'de>,
+{
+    struct EntriesVisitor<V>(PhantomData<V>);
+
+    impl<'de, V: Deserialize<'de>> serde::de::Visitor<'de> for EntriesVisitor<V> {
+        type Value = Vec<(String, V)>;
+
+        fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            f.write_str("a map")
+        }
+
+        fn visit_map<A: serde::de::MapAccess<'de>>(
+            self,
+            mut map: A,
+        ) -> Result<Self::Value, A::Error> {
+            let mut entries = Vec::new();
+            while let Some(entry) = map.next_entry()? {
+                entries.push(entry);
+            }
+            Ok(entries)
+        }
+    }
+
+    deserializer.deserialize_map(EntriesVisitor(PhantomData))
 }
 
 /// Response from /api/count
@@ -103,6 +414,43 @@ pub struct MessageResponse {
     pub message: String,
 }
 
+/// Error body from failed requests (`message` or `error`, depending on endpoint)
+#[derive(Debug, Clone, Deserialize)]
+pub struct ErrorResponse {
+    pub message: Option<String>,
+    pub error: Option<String>,
+}
+
+/// API endpoints fetched during a refresh
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Endpoint {
+    Counts,
+    Downloads,
+    System,
+    Logs,
+    Config,
+}
+
+impl Endpoint {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Endpoint::Counts => "counts",
+            Endpoint::Downloads => "downloads",
+            Endpoint::System => "system info",
+            Endpoint::Logs => "logs",
+            Endpoint::Config => "config",
+        }
+    }
+
+    pub const ALL: &[Endpoint] = &[
+        Endpoint::Counts,
+        Endpoint::Downloads,
+        Endpoint::System,
+        Endpoint::Logs,
+        Endpoint::Config,
+    ];
+}
+
 /// All data fetched from the server in one refresh cycle
 #[derive(Debug, Clone, Default)]
 pub struct RefreshData {
@@ -111,8 +459,64 @@ pub struct RefreshData {
     pub system: Option<SystemInfo>,
     pub logs: Vec<String>,
     pub config: Option<ConfigResponse>,
+    /// Endpoints that failed, whose fields above hold defaults
+    pub errors: Vec<(Endpoint, ApiError)>,
+}
+
+impl RefreshData {
+    /// A refresh in which every endpoint failed with the same error
+    pub fn failed(err: ApiError) -> Self {
+        RefreshData {
+            errors: Endpoint::ALL.iter().map(|e| (*e, err.clone())).collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Endpoints that failed during this refresh
+    pub fn failed_endpoints(&self) -> Vec<Endpoint> {
+        self.errors.iter().map(|(e, _)| *e).collect()
+    }
+}
+
+/// Health of the connection to the API server
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub enum ConnectionStatus {
+    /// No refresh has completed yet
+    #[default]
+    Connecting,
+    Connected,
+    /// The server answered, but some endpoints failed
+    Degraded(Vec<Endpoint>),
+    /// Every endpoint failed, starting at `since`
+    Unreachable {
+        since: SystemTime,
+    },
 }
 
+/// State of the live event stream
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum StreamStatus {
+    /// Not subscribed (yet); polling at the normal rate
+    #[default]
+    Disconnected,
+    /// Receiving pushed updates; polling only to resync
+    Live,
+    /// The server doesn't offer an event stream; polling only
+    Unsupported,
+}
+
+/// Ticks between full resyncs while the event stream is live
+const STREAM_RESYNC_TICKS: u32 = 20;
+
+/// Log lines kept in memory, matching what /api/logs returns
+const MAX_LOG_LINES: usize = 100;
+
+/// Refreshes happen this many times less often while the window is hidden
+const BACKGROUND_TICK_FACTOR: u32 = 10;
+
+/// Largest backoff exponent: refreshes slow down to every 2^4 ticks while unreachable
+const MAX_BACKOFF_SHIFT: u32 = 4;
+
 /// Download status filter for the downloads list
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub enum StatusFilter {
@@ -125,16 +529,23 @@ pub enum StatusFilter {
 }
 
 impl StatusFilter {
-    pub fn matches(&self, status: &str) -> bool {
+    /// The status shown, or `None` for all
+    pub fn status(self) -> Option<DownloadStatus> {
         match self {
-            StatusFilter::All => true,
-            StatusFilter::Pending => status == "pending",
-            StatusFilter::Downloading => status == "downloading",
-            StatusFilter::Success => status == "success",
-            StatusFilter::Error => status == "error",
+            StatusFilter::All => None,
+            StatusFilter::Pending => Some(DownloadStatus::Pending),
+            StatusFilter::Downloading => Some(DownloadStatus::Downloading),
+            StatusFilter::Success => Some(DownloadStatus::Success),
+            StatusFilter::Error => Some(DownloadStatus::Error),
         }
     }
 
+    /// The same condition as a query filter, or `None` for all
+    pub fn to_filter(self) -> Option<Filter> {
+        self.status()
+            .map(|status| Filter::Field(FilterField::Status, vec![status.to_string()]))
+    }
+
     pub fn label(&self) -> &'static str {
         match self {
             StatusFilter::All => "All",
@@ -154,43 +565,744 @@ impl StatusFilter {
     ];
 }
 
-/// Sort order for downloads list
+/// What the downloads list is sorted by
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
-pub enum SortOrder {
+pub enum SortKey {
     #[default]
-    NewestFirst,
-    OldestFirst,
+    Created,
+    Downloaded,
+    Title,
+    Status,
+    Priority,
+    /// Host of the URL
+    Domain,
     Collection,
 }
 
+impl SortKey {
+    pub fn label(&self) -> &'static str {
+        match self {
+            SortKey::Created => "Date Added",
+            SortKey::Downloaded => "Date Finished",
+            SortKey::Title => "Title",
+            SortKey::Status => "Status",
+            SortKey::Priority => "Priority",
+            SortKey::Domain => "Domain",
+            SortKey::Collection => "Collection",
+        }
+    }
+
+    /// Name in saved settings
+    pub fn name(&self) -> &'static str {
+        match self {
+            SortKey::Created => "created",
+            SortKey::Downloaded => "downloaded",
+            SortKey::Title => "title",
+            SortKey::Status => "status",
+            SortKey::Priority => "priority",
+            SortKey::Domain => "domain",
+            SortKey::Collection => "collection",
+        }
+    }
+
+    /// Direction the key starts in when picked: newest first for times,
+    /// ascending otherwise
+    pub fn default_descending(&self) -> bool {
+        matches!(self, SortKey::Created | SortKey::Downloaded)
+    }
+
+    /// What a download is compared by, or `None` for downloads that sort
+    /// last in either direction (no title, not finished, no host)
+    fn value(&self, dl: &Download) -> Option<SortValue> {
+        Some(match self {
+            SortKey::Created => SortValue::Time(dl.created_at?, dl.id),
+            SortKey::Downloaded => SortValue::Time(dl.downloaded_at?, dl.id),
+            SortKey::Title => {
+                let title = dl
+                    .title
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|t| !t.is_empty())?;
+                SortValue::Collated(gtk::glib::CollationKey::from(title))
+            }
+            // Pending, downloading, success, error, then unknown ones
+            SortKey::Status => SortValue::Rank(
+                DownloadStatus::KNOWN
+                    .iter()
+                    .position(|s| *s == dl.status)
+                    .unwrap_or(DownloadStatus::KNOWN.len()),
+            ),
+            // High first, then normal, then unknown ones
+            SortKey::Priority => SortValue::Rank(match dl.priority {
+                Priority::High => 0,
+                Priority::Normal => 1,
+                Priority::Unknown(_) => 2,
+            }),
+            SortKey::Domain => SortValue::Text(url_host(&dl.url)?),
+            SortKey::Collection => SortValue::Text(dl.collection.clone()),
+        })
+    }
+
+    pub const ALL: &[SortKey] = &[
+        SortKey::Created,
+        SortKey::Downloaded,
+        SortKey::Title,
+        SortKey::Status,
+        SortKey::Priority,
+        SortKey::Domain,
+        SortKey::Collection,
+    ];
+}
+
+/// A download's value for one [`SortKey`]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum SortValue {
+    /// A time, with the id breaking ties
+    Time(Timestamp, i64),
+    /// Text compared by the current locale's rules
+    Collated(gtk::glib::CollationKey),
+    Text(String),
+    Rank(usize),
+}
+
+/// Sort order for downloads list, saved as the key's name and direction
+/// (`title-asc`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub struct SortOrder {
+    pub key: SortKey,
+    pub descending: bool,
+}
+
+impl Default for SortOrder {
+    /// Most recently queued first
+    fn default() -> Self {
+        SortOrder::new(SortKey::default())
+    }
+}
+
 impl SortOrder {
+    /// Sort by `key` in its default direction
+    pub fn new(key: SortKey) -> Self {
+        SortOrder {
+            key,
+            descending: key.default_descending(),
+        }
+    }
+
+    pub fn reversed(self) -> Self {
+        SortOrder {
+            descending: !self.descending,
+            ..self
+        }
+    }
+
+    /// Sort downloads in this order. Downloads without a value for the key
+    /// go last, and ties are broken newest first.
+    pub fn sort(&self, downloads: &mut Vec<&Download>) {
+        let mut keyed: Vec<(Option<SortValue>, &Download)> = downloads
+            .drain(..)
+            .map(|d| (self.key.value(d), d))
+            .collect();
+        keyed.sort_by(|(a_value, a), (b_value, b)| {
+            let by_value = match (a_value, b_value) {
+                (Some(x), Some(y)) if self.descending => y.cmp(x),
+                (Some(x), Some(y)) => x.cmp(y),
+                (x, y) => x.is_none().cmp(&y.is_none()),
+            };
+            by_value.then_with(|| (b.created_at, b.id).cmp(&(a.created_at, a.id)))
+        });
+        downloads.extend(keyed.into_iter().map(|(_, d)| d));
+    }
+}
+
+impl From<String> for SortOrder {
+    /// Unrecognized names fall back to the default order
+    fn from(name: String) -> Self {
+        let (key, descending) = match name.rsplit_once('-') {
+            Some((key, "asc")) => (key, Some(false)),
+            Some((key, "desc")) => (key, Some(true)),
+            _ => (name.as_str(), None),
+        };
+        match SortKey::ALL.iter().find(|k| k.name() == key) {
+            Some(&key) => SortOrder {
+                key,
+                descending: descending.unwrap_or(key.default_descending()),
+            },
+            None => SortOrder::default(),
+        }
+    }
+}
+
+impl From<SortOrder> for String {
+    fn from(order: SortOrder) -> Self {
+        let direction = if order.descending { "desc" } else { "asc" };
+        format!("{}-{direction}", order.key.name())
+    }
+}
+
+/// How the Downloads page groups its list
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum GroupBy {
+    #[default]
+    None,
+    Collection,
+    /// Host of the URL
+    Domain,
+    Status,
+    /// Local date the download was added
+    Day,
+}
+
+impl GroupBy {
     pub fn label(&self) -> &'static str {
         match self {
-            SortOrder::NewestFirst => "Newest First",
-            SortOrder::OldestFirst => "Oldest First",
-            SortOrder::Collection => "Collection",
+            GroupBy::None => "No Grouping",
+            GroupBy::Collection => "Collection",
+            GroupBy::Domain => "Domain",
+            GroupBy::Status => "Status",
+            GroupBy::Day => "Day Added",
         }
     }
 
-    pub const ALL: &[SortOrder] = &[
-        SortOrder::NewestFirst,
-        SortOrder::OldestFirst,
-        SortOrder::Collection,
+    /// Key of the group `dl` belongs in; empty for downloads without a value
+    pub fn key(&self, dl: &Download) -> String {
+        match self {
+            GroupBy::None => String::new(),
+            GroupBy::Collection => dl.collection.clone(),
+            GroupBy::Domain => url_host(&dl.url)
+                .map(|host| registrable_domain(&host).to_string())
+                .unwrap_or_default(),
+            GroupBy::Status => dl.status.name().to_string(),
+            GroupBy::Day => dl.created_at.map(Timestamp::local_date).unwrap_or_default(),
+        }
+    }
+
+    /// Header title for a group key
+    fn title(&self, key: &str) -> String {
+        match (self, key) {
+            (GroupBy::Collection, "") => "No collection".into(),
+            (GroupBy::Domain, "") => "No domain".into(),
+            (GroupBy::Day, "") => "Unknown date".into(),
+            (GroupBy::Status, _) => {
+                let mut chars = key.chars();
+                chars
+                    .next()
+                    .map(|c| c.to_uppercase().chain(chars).collect())
+                    .unwrap_or_default()
+            }
+            _ => key.to_string(),
+        }
+    }
+
+    /// Order of groups: statuses as the server reports them, newest day
+    /// first, otherwise by name with the empty group last
+    fn compare(&self, a: &str, b: &str) -> std::cmp::Ordering {
+        match self {
+            GroupBy::Status => {
+                let rank = |key: &str| {
+                    let status = DownloadStatus::from(key);
+                    DownloadStatus::KNOWN
+                        .iter()
+                        .position(|s| *s == status)
+                        .unwrap_or(DownloadStatus::KNOWN.len())
+                };
+                rank(a).cmp(&rank(b)).then(a.cmp(b))
+            }
+            GroupBy::Day => b.cmp(a),
+            _ => (a.is_empty(), a).cmp(&(b.is_empty(), b)),
+        }
+    }
+
+    pub const ALL: &[GroupBy] = &[
+        GroupBy::None,
+        GroupBy::Collection,
+        GroupBy::Domain,
+        GroupBy::Status,
+        GroupBy::Day,
     ];
 }
 
+/// Downloads sharing a value of the [`GroupBy`] field, in list order
+#[derive(Debug)]
+pub struct DownloadGroup<'a> {
+    pub key: String,
+    pub title: String,
+    pub downloads: Vec<&'a Download>,
+}
+
+impl DownloadGroup<'_> {
+    /// The header shown above the group's downloads
+    pub fn header(&self, collapsed: bool) -> GroupHeader {
+        GroupHeader {
+            key: self.key.clone(),
+            title: self.title.clone(),
+            count: self.downloads.len(),
+            collapsed,
+            action_counts: DownloadAction::ALL
+                .iter()
+                .map(|&action| {
+                    let count = self
+                        .downloads
+                        .iter()
+                        .filter(|d| action.applies_to(&d.status))
+                        .count();
+                    (action, count)
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Header row of a group in the downloads list
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct GroupHeader {
+    pub key: String,
+    pub title: String,
+    pub count: usize,
+    pub collapsed: bool,
+    /// How many of the group's downloads each bulk action applies to
+    pub action_counts: Vec<(DownloadAction, usize)>,
+}
+
+/// A field that a query can filter on with `field:value`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterField {
+    Status,
+    Collection,
+    /// Host of the URL, including subdomains (`domain:reddit.com`)
+    Domain,
+    Priority,
+    Id,
+    /// Created on or after a local date (`after:2026-10-01`), or a date
+    /// relative to today (`after:-7d`, `after:yesterday`)
+    After,
+    /// Created before a local date
+    Before,
+    Title,
+    Url,
+    Error,
+}
+
+impl FilterField {
+    pub const ALL: &[FilterField] = &[
+        FilterField::Status,
+        FilterField::Collection,
+        FilterField::Domain,
+        FilterField::Priority,
+        FilterField::Id,
+        FilterField::After,
+        FilterField::Before,
+        FilterField::Title,
+        FilterField::Url,
+        FilterField::Error,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            FilterField::Status => "status",
+            FilterField::Collection => "collection",
+            FilterField::Domain => "domain",
+            FilterField::Priority => "priority",
+            FilterField::Id => "id",
+            FilterField::After => "after",
+            FilterField::Before => "before",
+            FilterField::Title => "title",
+            FilterField::Url => "url",
+            FilterField::Error => "error",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<FilterField> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|f| f.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Check a value when parsing, so mistakes are reported rather than
+    /// silently matching nothing
+    fn validate(&self, value: &str) -> Result<(), String> {
+        match self {
+            FilterField::Status
+                if matches!(DownloadStatus::from(value), DownloadStatus::Unknown(_)) =>
+            {
+                Err(format!(
+                    "unknown status '{value}' (expected one of: {})",
+                    DownloadStatus::KNOWN
+                        .iter()
+                        .map(|s| s.name())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ))
+            }
+            FilterField::Id if value.parse::<i64>().is_err() => {
+                Err(format!("'{value}' is not a download id"))
+            }
+            FilterField::After | FilterField::Before
+                if time::day_start(value, Timestamp::now()).is_none() =>
+            {
+                Err(format!(
+                    "'{value}' is not a date (use YYYY-MM-DD, today, yesterday, -7d or -2w)"
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn matches(&self, dl: &Download, value: &str) -> bool {
+        let contains = |text: &str| text.to_lowercase().contains(value);
+        match self {
+            FilterField::Status => dl.status.name() == value,
+            FilterField::Collection => dl.collection.eq_ignore_ascii_case(value),
+            FilterField::Domain => url_host(&dl.url)
+                .is_some_and(|host| host == value || host.ends_with(&format!(".{value}"))),
+            FilterField::Priority => dl.priority.name().eq_ignore_ascii_case(value),
+            FilterField::Id => value.parse() == Ok(dl.id),
+            // Parsed into `Filter::Created` instead
+            FilterField::After | FilterField::Before => false,
+            FilterField::Title => contains(dl.display_title()),
+            FilterField::Url => contains(&dl.url),
+            FilterField::Error => dl.error_message.as_deref().is_some_and(contains),
+        }
+    }
+}
+
+/// Lowercased host of a URL
+fn url_host(url: &str) -> Option<String> {
+    gtk::glib::Uri::parse(url, gtk::glib::UriFlags::NONE)
+        .ok()
+        .and_then(|uri| uri.host())
+        .map(|h| h.to_lowercase())
+}
+
+/// The part of `host` a site registers: `old.reddit.com` and
+/// `www.reddit.com` are both `reddit.com`, `news.bbc.co.uk` is `bbc.co.uk`.
+/// Two-part suffixes are recognized by shape (`co.uk`, `com.au`), which
+/// covers the common ones without a public suffix list.
+fn registrable_domain(host: &str) -> &str {
+    if host.parse::<std::net::IpAddr>().is_ok() || host.starts_with('[') {
+        return host;
+    }
+    let labels: Vec<&str> = host.split('.').collect();
+    let second_level = ["co", "com", "net", "org", "gov", "edu", "ac"];
+    let keep = match labels.as_slice() {
+        [.., sld, tld] if tld.len() == 2 && second_level.contains(sld) => 3,
+        _ => 2,
+    };
+    if labels.len() <= keep {
+        return host;
+    }
+    let start = labels[..labels.len() - keep]
+        .iter()
+        .map(|label| label.len() + 1)
+        .sum();
+    &host[start..]
+}
+
+/// A condition on downloads, parsed from a search query such as
+/// `status:error collection:yt domain:reddit.com -"geo restricted"`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Filter {
+    /// Lowercased text found in the title, URL, collection or error
+    /// message, or the exact download id
+    Text(String),
+    /// A field matching any of the values (`status:pending,error`)
+    Field(FilterField, Vec<String>),
+    /// `after:` or `before:` any of the days, each resolved to its local
+    /// midnight when the query is parsed
+    Created(FilterField, Vec<Timestamp>),
+    Not(Box<Filter>),
+    /// Every filter matches; empty matches everything
+    All(Vec<Filter>),
+}
+
+/// A query that doesn't parse, with the character offset of the problem
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryError {
+    pub message: String,
+    pub position: usize,
+}
+
+impl std::fmt::Display for QueryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} (at character {})", self.message, self.position + 1)
+    }
+}
+
+impl Filter {
+    /// Parse a search query. Words and `"quoted phrases"` are text to find,
+    /// `field:value` filters a field, and a leading `-` negates either; all
+    /// must match. Relative dates count back from now, so queries are
+    /// parsed again for each pass over the downloads.
+    pub fn parse(query: &str) -> Result<Filter, QueryError> {
+        let now = Timestamp::now();
+        let chars: Vec<char> = query.chars().collect();
+        let mut filters = Vec::new();
+        let mut pos = 0;
+
+        while pos < chars.len() {
+            if chars[pos].is_whitespace() {
+                pos += 1;
+                continue;
+            }
+            let negated =
+                chars[pos] == '-' && chars.get(pos + 1).is_some_and(|c| !c.is_whitespace());
+            if negated {
+                pos += 1;
+            }
+
+            // `field:` prefix, unless it's part of a URL (`https://...`)
+            let word_end = (pos..chars.len())
+                .find(|&i| !chars[i].is_ascii_alphabetic())
+                .unwrap_or(chars.len());
+            let field = if word_end > pos
+                && chars.get(word_end) == Some(&':')
+                && !(chars.get(word_end + 1) == Some(&'/') && chars.get(word_end + 2) == Some(&'/'))
+            {
+                let name: String = chars[pos..word_end].iter().collect();
+                let Some(field) = FilterField::from_name(&name) else {
+                    return Err(QueryError {
+                        message: format!(
+                            "unknown filter '{name}:' (expected one of: {})",
+                            FilterField::ALL
+                                .iter()
+                                .map(|f| f.name())
+                                .collect::<Vec<_>>()
+                                .join(", ")
+                        ),
+                        position: pos,
+                    });
+                };
+                pos = word_end + 1;
+                Some(field)
+            } else {
+                None
+            };
+
+            let value_start = pos;
+            let value: String = if chars.get(pos) == Some(&'"') {
+                let Some(close) = (pos + 1..chars.len()).find(|&i| chars[i] == '"') else {
+                    return Err(QueryError {
+                        message: "unclosed quote".into(),
+                        position: pos,
+                    });
+                };
+                pos = close + 1;
+                chars[value_start + 1..close].iter().collect()
+            } else {
+                while pos < chars.len() && !chars[pos].is_whitespace() {
+                    pos += 1;
+                }
+                chars[value_start..pos].iter().collect()
+            };
+            let value = value.trim().to_lowercase();
+
+            let filter = match field {
+                Some(field) => {
+                    let values: Vec<String> = value
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|v| !v.is_empty())
+                        .map(str::to_string)
+                        .collect();
+                    if values.is_empty() {
+                        return Err(QueryError {
+                            message: format!("'{}:' needs a value", field.name()),
+                            position: value_start,
+                        });
+                    }
+                    for v in &values {
+                        field.validate(v).map_err(|message| QueryError {
+                            message,
+                            position: value_start,
+                        })?;
+                    }
+                    match field {
+                        FilterField::After | FilterField::Before => Filter::Created(
+                            field,
+                            values
+                                .iter()
+                                .filter_map(|v| time::day_start(v, now))
+                                .collect(),
+                        ),
+                        _ => Filter::Field(field, values),
+                    }
+                }
+                None if value.is_empty() => continue,
+                None => Filter::Text(value),
+            };
+            filters.push(if negated {
+                Filter::Not(Box::new(filter))
+            } else {
+                filter
+            });
+        }
+        Ok(Filter::All(filters))
+    }
+
+    pub fn matches(&self, dl: &Download) -> bool {
+        match self {
+            Filter::Text(text) => {
+                text.strip_prefix('#').unwrap_or(text) == dl.id.to_string()
+                    || [
+                        dl.title.as_deref().unwrap_or_default(),
+                        &dl.url,
+                        &dl.collection,
+                        dl.error_message.as_deref().unwrap_or_default(),
+                    ]
+                    .iter()
+                    .any(|field| field.to_lowercase().contains(text.as_str()))
+            }
+            Filter::Field(field, values) => values.iter().any(|v| field.matches(dl, v)),
+            Filter::Created(field, days) => dl.created_at.is_some_and(|t| {
+                days.iter().any(|&day| match field {
+                    FilterField::Before => t < day,
+                    _ => t >= day,
+                })
+            }),
+            Filter::Not(filter) => !filter.matches(dl),
+            Filter::All(filters) => filters.iter().all(|f| f.matches(dl)),
+        }
+    }
+
+    /// Text worth highlighting in matching rows: searched words and phrases,
+    /// and the values of text fields, but nothing negated
+    pub fn highlight_terms(&self) -> Vec<String> {
+        match self {
+            Filter::Text(text) => vec![text.clone()],
+            Filter::Field(
+                FilterField::Title | FilterField::Url | FilterField::Error | FilterField::Domain,
+                values,
+            ) => values.clone(),
+            Filter::Field(..) | Filter::Created(..) | Filter::Not(_) => Vec::new(),
+            Filter::All(filters) => filters.iter().flat_map(|f| f.highlight_terms()).collect(),
+        }
+    }
+}
+
+/// Quote a query value if it would otherwise end early
+fn quote_value(value: &str) -> String {
+    if value.contains(char::is_whitespace) {
+        format!("\"{value}\"")
+    } else {
+        value.to_string()
+    }
+}
+
+/// A named search query and sort order, listed in the sidebar
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SavedView {
+    pub name: String,
+    pub query: String,
+    #[serde(default)]
+    pub sort: SortOrder,
+}
+
+/// An entry in the sidebar
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SidebarItem {
+    /// A page of the window, by the name `--show` takes
+    Page(&'static str),
+    /// The downloads in one collection of the server config
+    Collection(String),
+    View(SavedView),
+}
+
+impl SidebarItem {
+    /// The pages, then one entry per configured collection, then the saved views
+    pub fn list(
+        pages: &[&'static str],
+        config: Option<&ConfigResponse>,
+        views: &[SavedView],
+    ) -> Vec<SidebarItem> {
+        let collections = config.map(|c| c.collections.as_slice()).unwrap_or_default();
+        pages
+            .iter()
+            .map(|&page| SidebarItem::Page(page))
+            .chain(
+                collections
+                    .iter()
+                    .map(|(name, _)| SidebarItem::Collection(name.clone())),
+            )
+            .chain(views.iter().cloned().map(SidebarItem::View))
+            .collect()
+    }
+
+    /// Identifies the entry across rebuilds, even if a view's query changed
+    pub fn key(&self) -> String {
+        match self {
+            SidebarItem::Page(page) => format!("page:{page}"),
+            SidebarItem::Collection(name) => format!("collection:{name}"),
+            SidebarItem::View(view) => format!("view:{}", view.name),
+        }
+    }
+
+    /// Heading of the section the entry belongs to
+    pub fn section(&self) -> Option<&'static str> {
+        match self {
+            SidebarItem::Page(_) => None,
+            SidebarItem::Collection(_) => Some("Collections"),
+            SidebarItem::View(_) => Some("Saved Views"),
+        }
+    }
+
+    /// The page shown when the entry is selected
+    pub fn page(&self) -> &'static str {
+        match self {
+            SidebarItem::Page(page) => page,
+            _ => "downloads",
+        }
+    }
+
+    /// Query for the downloads the entry counts, and the Downloads page
+    /// shows when it's selected; `None` for pages without downloads
+    pub fn query(&self) -> Option<String> {
+        match self {
+            SidebarItem::Page("downloads") => Some(String::new()),
+            SidebarItem::Page("errors") => Some("status:error".into()),
+            SidebarItem::Page(_) => None,
+            SidebarItem::Collection(name) => Some(format!("collection:{}", quote_value(name))),
+            SidebarItem::View(view) => Some(view.query.clone()),
+        }
+    }
+}
+
 /// Main application state
 #[derive(Debug, Default)]
 pub struct AppState {
     pub counts: Vec<StatusCount>,
     pub downloads: Vec<Download>,
     pub system: Option<SystemInfo>,
-    pub logs: Vec<String>,
+    pub logs: Vec<LogEntry>,
     pub config: Option<ConfigResponse>,
     pub status_filter: StatusFilter,
     pub sort_order: SortOrder,
+    pub group_by: GroupBy,
+    /// Keys of the groups shown collapsed
+    pub collapsed_groups: HashSet<String>,
     pub log_filter: LogFilter,
     pub log_search: String,
+    /// Search query for the Downloads page
+    pub download_search: String,
+    /// Query of the sidebar entry shown on the Downloads page
+    pub view_query: String,
+    pub connection: ConnectionStatus,
+    /// Consecutive refreshes in which the server was unreachable
+    pub failed_refreshes: u32,
+    /// Auto-refresh ticks left before the next refresh is due
+    pub ticks_until_refresh: u32,
+    pub stream: StreamStatus,
+    /// Incremented each time the event stream is (re)started, so updates from
+    /// a stale subscription can be told apart
+    pub stream_generation: u64,
+    /// A bulk action over the selection is running
+    pub bulk_running: bool,
+    /// The window is hidden and the app keeps running in the background
+    pub background: bool,
+    /// Last copied URL offered for queueing, so it isn't offered twice
+    pub clipboard_offered: Option<String>,
 }
 
 /// Log filter type
@@ -199,73 +1311,226 @@ pub enum LogFilter {
     #[default]
     All,
     Errors,
-    Warnings,
+    Debug,
     Info,
 }
 
+impl LogFilter {
+    pub fn matches(&self, entry: &LogEntry) -> bool {
+        match self {
+            LogFilter::All => true,
+            LogFilter::Errors => entry.level == LogLevel::Error,
+            LogFilter::Debug => entry.level == LogLevel::Debug,
+            LogFilter::Info => entry.level == LogLevel::Info,
+        }
+    }
+}
+
 impl AppState {
     /// Get the count for a given status, defaulting to 0
-    pub fn count_for(&self, status: &str) -> i64 {
+    pub fn count_for(&self, status: &DownloadStatus) -> i64 {
         self.counts
             .iter()
-            .find(|c| c.status == status)
+            .find(|c| c.status == *status)
             .map(|c| c.count)
             .unwrap_or(0)
     }
 
-    /// Get downloads filtered by current status filter and sorted
+    /// Update connection health from the endpoints that failed in a refresh
+    pub fn record_refresh(&mut self, failed: Vec<Endpoint>, now: SystemTime) {
+        if failed.len() == Endpoint::ALL.len() {
+            if !matches!(self.connection, ConnectionStatus::Unreachable { .. }) {
+                self.connection = ConnectionStatus::Unreachable { since: now };
+            }
+            self.failed_refreshes += 1;
+        } else {
+            self.connection = if failed.is_empty() {
+                ConnectionStatus::Connected
+            } else {
+                ConnectionStatus::Degraded(failed)
+            };
+            self.failed_refreshes = 0;
+        }
+        self.ticks_until_refresh = self.backoff_ticks();
+    }
+
+    /// Number of auto-refresh ticks between attempts, doubling with each
+    /// consecutive failure. While the event stream is live, polling only
+    /// resyncs occasionally. Everything slows down while running in the
+    /// background.
+    pub fn backoff_ticks(&self) -> u32 {
+        let ticks = if self.stream == StreamStatus::Live {
+            STREAM_RESYNC_TICKS
+        } else {
+            1 << self.failed_refreshes.min(MAX_BACKOFF_SHIFT)
+        };
+        if self.background {
+            ticks * BACKGROUND_TICK_FACTOR
+        } else {
+            ticks
+        }
+    }
+
+    /// Advance the auto-refresh timer by one tick, returning whether a
+    /// refresh is due
+    pub fn tick(&mut self) -> bool {
+        if self.ticks_until_refresh > 1 {
+            self.ticks_until_refresh -= 1;
+            false
+        } else {
+            self.ticks_until_refresh = self.backoff_ticks();
+            true
+        }
+    }
+
+    /// Forget past failures so the next attempt happens on the next tick
+    pub fn reset_backoff(&mut self) {
+        self.failed_refreshes = 0;
+        self.ticks_until_refresh = 0;
+    }
+
+    /// Apply an update pushed over the event stream
+    pub fn apply_event(&mut self, event: ServerEvent) {
+        match event {
+            ServerEvent::Download(dl) => {
+                match self.downloads.iter_mut().find(|d| d.id == dl.id) {
+                    Some(existing) => *existing = dl,
+                    None => self.downloads.push(dl),
+                }
+                self.recount();
+            }
+            ServerEvent::Deleted(id) => {
+                self.downloads.retain(|d| d.id != id);
+                self.recount();
+            }
+            ServerEvent::Log(line) => {
+                log_entry::push_line(&mut self.logs, &line);
+                let excess = self.logs.len().saturating_sub(MAX_LOG_LINES);
+                self.logs.drain(..excess);
+            }
+        }
+    }
+
+    /// Recompute status counts from the downloads list
+    fn recount(&mut self) {
+        let mut counts: Vec<StatusCount> = Vec::new();
+        for dl in &self.downloads {
+            match counts.iter_mut().find(|c| c.status == dl.status) {
+                Some(c) => c.count += 1,
+                None => counts.push(StatusCount {
+                    status: dl.status.clone(),
+                    count: 1,
+                }),
+            }
+        }
+        self.counts = counts;
+    }
+
+    /// The Downloads page's sidebar view, status toggle and search query
+    /// combined. A query that doesn't parse is left out (the entry shows the
+    /// error).
+    pub fn download_filter(&self) -> Filter {
+        let filters = Filter::parse(&self.view_query)
+            .ok()
+            .into_iter()
+            .chain(self.status_filter.to_filter())
+            .chain(Filter::parse(&self.download_search).ok())
+            .collect();
+        Filter::All(filters)
+    }
+
+    /// The combined filter as one query, for saving as a view
+    pub fn current_query(&self) -> String {
+        let status = self.status_filter.status().map(|s| format!("status:{s}"));
+        [
+            Some(self.view_query.trim().to_string()),
+            status,
+            Some(self.download_search.trim().to_string()),
+        ]
+        .into_iter()
+        .flatten()
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+    }
+
+    /// Number of downloads matching a query, or `None` if it doesn't parse
+    pub fn count_matching(&self, query: &str) -> Option<usize> {
+        let filter = Filter::parse(query).ok()?;
+        Some(self.downloads.iter().filter(|d| filter.matches(d)).count())
+    }
+
+    /// Get downloads matching the status toggle and search query, sorted
     pub fn filtered_downloads(&self) -> Vec<&Download> {
+        let filter = self.download_filter();
         let mut result: Vec<&Download> = self
             .downloads
             .iter()
-            .filter(|d| self.status_filter.matches(&d.status))
+            .filter(|d| filter.matches(d))
             .collect();
 
-        match self.sort_order {
-            SortOrder::NewestFirst => result.sort_by(|a, b| b.id.cmp(&a.id)),
-            SortOrder::OldestFirst => result.sort_by(|a, b| a.id.cmp(&b.id)),
-            SortOrder::Collection => {
-                result.sort_by(|a, b| a.collection.cmp(&b.collection).then(b.id.cmp(&a.id)))
+        self.sort_order.sort(&mut result);
+        result
+    }
+
+    /// Filtered downloads split into groups, keeping the sort order within
+    /// each; a single untitled group when not grouping
+    pub fn grouped_downloads(&self) -> Vec<DownloadGroup<'_>> {
+        let mut groups: Vec<DownloadGroup> = Vec::new();
+        let mut index: HashMap<String, usize> = HashMap::new();
+        for dl in self.filtered_downloads() {
+            let key = self.group_by.key(dl);
+            match index.get(&key) {
+                Some(&i) => groups[i].downloads.push(dl),
+                None => {
+                    index.insert(key.clone(), groups.len());
+                    groups.push(DownloadGroup {
+                        title: self.group_by.title(&key),
+                        key,
+                        downloads: vec![dl],
+                    });
+                }
             }
         }
-
-        result
+        groups.sort_by(|a, b| self.group_by.compare(&a.key, &b.key));
+        groups
     }
 
     /// Get error downloads
     pub fn error_downloads(&self) -> Vec<&Download> {
         self.downloads
             .iter()
-            .filter(|d| d.status == "error")
+            .filter(|d| d.status == DownloadStatus::Error)
             .collect()
     }
 
+    /// The collection the server would file `url` under, if the config is
+    /// loaded and a collection matches
+    pub fn route_url(&self, url: &str) -> Option<&str> {
+        self.config.as_ref().and_then(|c| c.route(url))
+    }
+
+    /// An existing download for exactly this URL
+    pub fn download_for_url(&self, url: &str) -> Option<&Download> {
+        self.downloads.iter().find(|d| d.url == url)
+    }
+
     /// Look up the directory for a download's collection
     pub fn dir_for_collection(&self, collection: &str) -> Option<&str> {
         self.config
             .as_ref()
-            .and_then(|c| c.collections.get(collection))
+            .and_then(|c| c.collection(collection))
             .map(|c| c.dir.as_str())
     }
 
     /// Get filtered log lines based on current filter and search
-    pub fn filtered_logs(&self) -> Vec<&str> {
+    pub fn filtered_logs(&self) -> Vec<&LogEntry> {
+        let search = self.log_search.to_lowercase();
         self.logs
             .iter()
             .rev()
-            .filter(|line| match self.log_filter {
-                LogFilter::All => true,
-                LogFilter::Errors => line.contains("ERROR") || line.contains("error"),
-                LogFilter::Warnings => line.contains("WARN") || line.contains("warn"),
-                LogFilter::Info => line.contains("INFO") || line.contains("info"),
-            })
-            .filter(|line| {
-                self.log_search.is_empty()
-                    || line
-                        .to_lowercase()
-                        .contains(&self.log_search.to_lowercase())
-            })
-            .map(|s| s.as_str())
+            .filter(|entry| self.log_filter.matches(entry))
+            .filter(|entry| search.is_empty() || entry.text.to_lowercase().contains(&search))
             .collect()
     }
 }
@@ -273,17 +1538,46 @@ impl AppState {
 /// Collection of UI widgets that need to be accessed throughout the app
 pub struct Widgets {
     pub toast_overlay: adw::ToastOverlay,
+    pub connection_banner: adw::Banner,
     // Stats labels
     pub pending_label: gtk::Label,
     pub downloading_label: gtk::Label,
     pub success_label: gtk::Label,
     pub error_count_label: gtk::Label,
     pub system_label: gtk::Label,
+    // Navigation
+    pub split_view: adw::NavigationSplitView,
+    pub content_page: adw::NavigationPage,
+    pub view_stack: adw::ViewStack,
+    pub sidebar_list: gtk::ListBox,
+    /// Entry of each `sidebar_list` row, by index
+    pub sidebar_items: Rc<RefCell<Vec<SidebarItem>>>,
+    /// Count badge of each `sidebar_list` row, by index
+    pub sidebar_counts: RefCell<Vec<gtk::Label>>,
     // Downloads page
-    pub downloads_list: gtk::ListBox,
+    pub status_buttons: Vec<(StatusFilter, gtk::ToggleButton)>,
+    pub downloads_sort: gtk::DropDown,
+    pub downloads_sort_direction: gtk::Button,
+    pub downloads_group: gtk::DropDown,
+    pub downloads_search_bar: gtk::SearchBar,
+    pub downloads_search: gtk::SearchEntry,
+    pub downloads_search_error: gtk::Label,
+    /// What the download rows are bound with
+    pub downloads_context: Rc<RefCell<RowContext>>,
+    pub downloads_store: gtk::gio::ListStore,
+    pub downloads_selection: gtk::MultiSelection,
+    pub downloads_list: gtk::ListView,
+    pub downloads_stack: gtk::Stack,
+    pub downloads_empty: adw::StatusPage,
     pub downloads_count_label: gtk::Label,
+    pub selection_bar: gtk::ActionBar,
+    pub selection_label: gtk::Label,
+    pub selection_progress: gtk::ProgressBar,
+    pub selection_buttons: Vec<(DownloadAction, gtk::Button)>,
     // Errors page
-    pub errors_list: gtk::ListBox,
+    pub errors_context: Rc<RefCell<RowContext>>,
+    pub errors_store: gtk::gio::ListStore,
+    pub errors_list: gtk::ListView,
     pub errors_stack: gtk::Stack,
     // Logs page
     pub logs_buffer: gtk::TextBuffer,
@@ -306,9 +1600,18 @@ impl std::fmt::Debug for Widgets {
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
 
+    /// A download with just an id and status, for tests to fill in further
+    pub(crate) fn download(id: i64, status: &str) -> Download {
+        Download {
+            id,
+            status: status.into(),
+            ..Default::default()
+        }
+    }
+
     #[test]
     fn test_download_display_title() {
         let d1 = Download {
@@ -324,6 +1627,55 @@ mod tests {
         assert_eq!(d2.display_title(), "Untitled");
     }
 
+    #[test]
+    fn test_download_action_for_status() {
+        assert_eq!(
+            DownloadAction::for_status(&DownloadStatus::Error),
+            Some(DownloadAction::Retry)
+        );
+        assert_eq!(
+            DownloadAction::for_status(&DownloadStatus::Pending),
+            Some(DownloadAction::Delete)
+        );
+        assert_eq!(
+            DownloadAction::for_status(&DownloadStatus::Success),
+            Some(DownloadAction::Redownload)
+        );
+        assert_eq!(
+            DownloadAction::for_status(&DownloadStatus::Downloading),
+            Some(DownloadAction::Reset)
+        );
+        assert_eq!(DownloadAction::for_status(&"paused".into()), None);
+    }
+
+    #[test]
+    fn test_download_action_applies_to() {
+        assert!(DownloadAction::Retry.applies_to(&DownloadStatus::Error));
+        assert!(!DownloadAction::Retry.applies_to(&DownloadStatus::Pending));
+        assert!(DownloadAction::Delete.applies_to(&DownloadStatus::Pending));
+        assert!(DownloadAction::Delete.applies_to(&DownloadStatus::Error));
+        assert!(!DownloadAction::Delete.applies_to(&DownloadStatus::Downloading));
+        assert!(DownloadAction::Reset.applies_to(&DownloadStatus::Downloading));
+        assert!(DownloadAction::Redownload.applies_to(&DownloadStatus::Success));
+    }
+
+    #[test]
+    fn test_bulk_summary() {
+        assert_eq!(
+            DownloadAction::Delete.summary(40, &[]),
+            "Delete: 40 of 40 succeeded"
+        );
+
+        let failures: Vec<(i64, String)> = (1..=5)
+            .map(|id| (id, "Download not found".to_string()))
+            .collect();
+        assert_eq!(
+            DownloadAction::Retry.summary(10, &failures),
+            "Retry: 5 of 10 succeeded · failed: #1 (Download not found), \
+             #2 (Download not found), #3 (Download not found) and 2 more"
+        );
+    }
+
     #[test]
     fn test_count_for() {
         let state = AppState {
@@ -339,19 +1691,139 @@ mod tests {
             ],
             ..Default::default()
         };
-        assert_eq!(state.count_for("pending"), 5);
-        assert_eq!(state.count_for("error"), 2);
-        assert_eq!(state.count_for("success"), 0);
+        assert_eq!(state.count_for(&DownloadStatus::Pending), 5);
+        assert_eq!(state.count_for(&DownloadStatus::Error), 2);
+        assert_eq!(state.count_for(&DownloadStatus::Success), 0);
     }
 
     #[test]
     fn test_status_filter() {
-        assert!(StatusFilter::All.matches("pending"));
-        assert!(StatusFilter::All.matches("error"));
-        assert!(StatusFilter::Pending.matches("pending"));
-        assert!(!StatusFilter::Pending.matches("error"));
-        assert!(StatusFilter::Error.matches("error"));
-        assert!(!StatusFilter::Error.matches("success"));
+        let matches = |filter: StatusFilter, status: &str| {
+            let dl = Download {
+                status: status.into(),
+                ..Default::default()
+            };
+            filter.to_filter().is_none_or(|f| f.matches(&dl))
+        };
+        assert!(matches(StatusFilter::All, "pending"));
+        assert!(matches(StatusFilter::All, "error"));
+        assert!(matches(StatusFilter::Pending, "pending"));
+        assert!(!matches(StatusFilter::Pending, "error"));
+        assert!(matches(StatusFilter::Error, "error"));
+        assert!(!matches(StatusFilter::Error, "success"));
+    }
+
+    fn query_dl() -> Download {
+        Download {
+            id: 42,
+            collection: "yt".into(),
+            // Noon local time, so date filters hold in every time zone
+            created_at: gtk::glib::DateTime::from_local(2026, 10, 5, 12, 0, 0.0)
+                .ok()
+                .map(|t| Timestamp(t.to_unix() * 1000)),
+            priority: "high".into(),
+            status: "error".into(),
+            title: Some("Rust Conference Talk".into()),
+            url: "https://old.reddit.com/r/rust/comments/abc".into(),
+            error_message: Some("Video is geo restricted in your country".into()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_filter_parse() {
+        use FilterField::*;
+        let filter = Filter::parse(
+            r#"status:error collection:YT domain:reddit.com after:2026-10-01 priority:high -"geo restricted" rust"#,
+        )
+        .unwrap();
+        assert_eq!(
+            filter,
+            Filter::All(vec![
+                Filter::Field(Status, vec!["error".into()]),
+                Filter::Field(Collection, vec!["yt".into()]),
+                Filter::Field(Domain, vec!["reddit.com".into()]),
+                Filter::Created(
+                    After,
+                    vec![time::day_start("2026-10-01", Timestamp(0)).unwrap()]
+                ),
+                Filter::Field(Priority, vec!["high".into()]),
+                Filter::Not(Box::new(Filter::Text("geo restricted".into()))),
+                Filter::Text("rust".into()),
+            ])
+        );
+        assert_eq!(Filter::parse("  ").unwrap(), Filter::All(vec![]));
+        assert_eq!(
+            Filter::parse("status:pending,error title:\"two words\"").unwrap(),
+            Filter::All(vec![
+                Filter::Field(Status, vec!["pending".into(), "error".into()]),
+                Filter::Field(Title, vec!["two words".into()]),
+            ])
+        );
+        // URLs and lone dashes are plain text
+        assert_eq!(
+            Filter::parse("https://youtu.be/x -").unwrap(),
+            Filter::All(vec![
+                Filter::Text("https://youtu.be/x".into()),
+                Filter::Text("-".into()),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_filter_parse_errors() {
+        let err = Filter::parse("rust colour:red").unwrap_err();
+        assert!(err.message.starts_with("unknown filter 'colour:'"));
+        assert_eq!(err.position, 5);
+
+        let err = Filter::parse("status:failed").unwrap_err();
+        assert!(err.message.contains("unknown status 'failed'"));
+        assert_eq!(err.position, 7);
+
+        assert!(Filter::parse("status:").is_err());
+        assert!(Filter::parse("id:abc").is_err());
+        assert!(Filter::parse("after:10/01/2026").is_err());
+        assert!(Filter::parse("after:-7x").is_err());
+        assert!(Filter::parse("before:tomorrow").is_err());
+        let err = Filter::parse(r#"-"geo restricted"#).unwrap_err();
+        assert_eq!(err.message, "unclosed quote");
+        assert_eq!(err.position, 1);
+    }
+
+    #[test]
+    fn test_filter_matches() {
+        let dl = query_dl();
+        let matches = |query: &str| Filter::parse(query).unwrap().matches(&dl);
+        assert!(matches(""));
+        assert!(matches(
+            "status:error collection:yt domain:reddit.com priority:high"
+        ));
+        assert!(matches("domain:old.reddit.com rust talk"));
+        assert!(!matches("domain:ddit.com"));
+        assert!(matches("after:2026-10-05 before:2026-10-06"));
+        assert!(!matches("after:2026-10-06"));
+        assert!(!matches("before:2026-10-05"));
+        assert!(matches("after:-100000d"));
+        assert!(!matches("after:today"));
+        assert!(matches("before:-1w"));
+        assert!(Filter::parse("after:-7d before:yesterday").is_ok());
+        assert!(matches("#42 id:42"));
+        assert!(!matches("id:4"));
+        assert!(matches("status:pending,error"));
+        assert!(!matches("-\"geo restricted\""));
+        assert!(matches("-status:success error:geo"));
+        assert!(!matches("title:reddit"));
+        assert!(matches("url:reddit"));
+    }
+
+    #[test]
+    fn test_filter_highlight_terms() {
+        let filter =
+            Filter::parse(r#"rust status:error url:reddit -geo "conference talk""#).unwrap();
+        assert_eq!(
+            filter.highlight_terms(),
+            vec!["rust", "reddit", "conference talk"]
+        );
     }
 
     #[test]
@@ -376,48 +1848,181 @@ mod tests {
         };
         let filtered = state.filtered_downloads();
         assert_eq!(filtered.len(), 1);
-        assert_eq!(filtered[0].status, "pending");
+        assert_eq!(filtered[0].status, DownloadStatus::Pending);
     }
 
     #[test]
-    fn test_log_filter() {
-        let state = AppState {
-            logs: vec![
-                "INFO: started".into(),
-                "ERROR: failed".into(),
-                "WARN: slow".into(),
-                "INFO: done".into(),
+    fn test_filtered_downloads_search() {
+        let mut state = AppState {
+            downloads: vec![
+                Download {
+                    title: Some("Rust talk".into()),
+                    ..download(1, "pending")
+                },
+                Download {
+                    title: Some("Rust workshop".into()),
+                    ..download(2, "error")
+                },
+                Download {
+                    title: Some("Cooking".into()),
+                    ..download(3, "pending")
+                },
+            ],
+            download_search: "rust".into(),
+            ..Default::default()
+        };
+        let ids: Vec<i64> = state.filtered_downloads().iter().map(|d| d.id).collect();
+        assert_eq!(ids, vec![2, 1]);
+
+        // Search narrows the status filter rather than replacing it
+        state.status_filter = StatusFilter::Pending;
+        let ids: Vec<i64> = state.filtered_downloads().iter().map(|d| d.id).collect();
+        assert_eq!(ids, vec![1]);
+    }
+
+    #[test]
+    fn test_view_query_and_current_query() {
+        let mut state = AppState {
+            downloads: vec![
+                Download {
+                    collection: "yt".into(),
+                    ..download(1, "pending")
+                },
+                Download {
+                    collection: "yt".into(),
+                    ..download(2, "error")
+                },
+                Download {
+                    collection: "My Art".into(),
+                    ..download(3, "error")
+                },
             ],
+            ..Default::default()
+        };
+        assert_eq!(state.current_query(), "");
+
+        state.view_query = "collection:yt".into();
+        state.status_filter = StatusFilter::Error;
+        let ids: Vec<i64> = state.filtered_downloads().iter().map(|d| d.id).collect();
+        assert_eq!(ids, vec![2]);
+
+        state.download_search = " -workshop ".into();
+        assert_eq!(
+            state.current_query(),
+            "collection:yt status:error -workshop"
+        );
+
+        let art = SidebarItem::Collection("My Art".into()).query().unwrap();
+        assert_eq!(art, "collection:\"My Art\"");
+        assert_eq!(state.count_matching(&art), Some(1));
+        assert_eq!(state.count_matching(""), Some(3));
+        assert_eq!(state.count_matching("status:nope"), None);
+    }
+
+    #[test]
+    fn test_sidebar_items() {
+        let config: ConfigResponse = serde_json::from_str(
+            r#"{"collections":{"yt":{"dir":"/v","command":"yt-dlp %","domains":[]}}}"#,
+        )
+        .unwrap();
+        let view = SavedView {
+            name: "Reddit errors".into(),
+            query: "domain:reddit.com status:error".into(),
+            sort: SortOrder::new(SortKey::Title),
+        };
+        let items = SidebarItem::list(
+            &["downloads", "errors", "logs"],
+            Some(&config),
+            std::slice::from_ref(&view),
+        );
+        let keys: Vec<String> = items.iter().map(|i| i.key()).collect();
+        assert_eq!(
+            keys,
+            vec![
+                "page:downloads",
+                "page:errors",
+                "page:logs",
+                "collection:yt",
+                "view:Reddit errors"
+            ]
+        );
+        let sections: Vec<Option<&str>> = items.iter().map(|i| i.section()).collect();
+        assert_eq!(
+            sections,
+            vec![None, None, None, Some("Collections"), Some("Saved Views")]
+        );
+        assert_eq!(items[2].page(), "logs");
+        assert_eq!(items[2].query(), None);
+        assert_eq!(items[3].page(), "downloads");
+        assert_eq!(items[4].query().as_deref(), Some(view.query.as_str()));
+
+        // Before the config loads there are no collections
+        assert_eq!(SidebarItem::list(&["downloads"], None, &[]).len(), 1);
+    }
+
+    fn logs(lines: &[&str]) -> Vec<LogEntry> {
+        LogEntry::parse_all(lines.iter().copied())
+    }
+
+    fn texts<'a>(entries: &[&'a LogEntry]) -> Vec<&'a str> {
+        entries.iter().map(|e| e.text.as_str()).collect()
+    }
+
+    #[test]
+    fn test_log_filter() {
+        let mut state = AppState {
+            logs: logs(&[
+                "[2026-10-01T12:00:00.000Z] downloading: https://example.com/error-page (id: 1)",
+                "[ERROR] [2026-10-01T12:00:01.000Z] error: Talk (id: 2): ERROR: exit code 1",
+                "  caused by: network unreachable",
+                "added https://example.com/3 to db",
+                "[DEBUG] queue empty",
+                "daemon: done",
+            ]),
             log_filter: LogFilter::Errors,
             ..Default::default()
         };
+        // A URL containing "error" isn't an error; the command's output is
+        // part of the error, the server's next line isn't
         let filtered = state.filtered_logs();
         assert_eq!(filtered.len(), 1);
-        assert_eq!(filtered[0], "ERROR: failed");
+        assert_eq!(filtered[0].ids[0].0, 2);
+        assert!(
+            filtered[0]
+                .text
+                .ends_with("\n  caused by: network unreachable")
+        );
+        state.log_filter = LogFilter::Debug;
+        assert_eq!(texts(&state.filtered_logs()), vec!["[DEBUG] queue empty"]);
+        state.log_filter = LogFilter::Info;
+        assert_eq!(
+            texts(&state.filtered_logs())[..2],
+            ["daemon: done", "added https://example.com/3 to db"]
+        );
+        assert_eq!(state.filtered_logs().len(), 3);
     }
 
     #[test]
     fn test_logs_reversed() {
         let state = AppState {
-            logs: vec!["first".into(), "second".into(), "third".into()],
+            logs: logs(&["first", "second", "third"]),
             log_filter: LogFilter::All,
             ..Default::default()
         };
         let filtered = state.filtered_logs();
-        assert_eq!(filtered, vec!["third", "second", "first"]);
+        assert_eq!(texts(&filtered), vec!["third", "second", "first"]);
     }
 
     #[test]
     fn test_log_search() {
         let state = AppState {
-            logs: vec!["download started".into(), "upload finished".into()],
+            logs: logs(&["download started", "upload finished"]),
             log_filter: LogFilter::All,
-            log_search: "download".into(),
+            log_search: "Download".into(),
             ..Default::default()
         };
         let filtered = state.filtered_logs();
-        assert_eq!(filtered.len(), 1);
-        assert!(filtered[0].contains("download"));
+        assert_eq!(texts(&filtered), vec!["download started"]);
     }
 
     #[test]
@@ -430,14 +2035,14 @@ mod tests {
     fn test_dir_for_collection() {
         let state = AppState {
             config: Some(ConfigResponse {
-                collections: HashMap::from([(
+                collections: vec![(
                     "yt".into(),
                     CollectionConfig {
                         dir: "/home/user/videos".into(),
                         command: "yt-dlp %".into(),
                         domains: vec!["youtube.com".into()],
                     },
-                )]),
+                )],
             }),
             ..Default::default()
         };
@@ -446,32 +2051,276 @@ mod tests {
     }
 
     #[test]
-    fn test_sort_newest_first() {
-        let state = AppState {
+    fn test_config_keeps_order_and_routes() {
+        let json = r#"{"collections":{
+            "shorts":{"dir":"/s","command":"yt-dlp %","domains":["youtube.com/shorts"]},
+            "yt":{"dir":"/v","command":"yt-dlp %","domains":["youtube.com","youtu.be"]},
+            "art":{"dir":"/a","command":"gallery-dl %","domains":["example.art"]}
+        }}"#;
+        let config: ConfigResponse = serde_json::from_str(json).unwrap();
+        let names: Vec<&str> = config.collections.iter().map(|(n, _)| n.as_str()).collect();
+        assert_eq!(names, vec!["shorts", "yt", "art"]);
+
+        assert_eq!(
+            config.route("https://youtube.com/shorts/abc"),
+            Some("shorts")
+        );
+        assert_eq!(config.route("https://youtu.be/abc"), Some("yt"));
+        assert_eq!(config.route("https://unknown.example/x"), None);
+        assert_eq!(config.collection("art").map(|c| c.dir.as_str()), Some("/a"));
+        assert!(config.collection("missing").is_none());
+    }
+
+    #[test]
+    fn test_route_and_find_url() {
+        let mut state = AppState {
+            downloads: vec![Download {
+                id: 4,
+                url: "https://youtu.be/x".into(),
+                status: "success".into(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        assert_eq!(state.route_url("https://youtu.be/x"), None);
+
+        state.config = Some(ConfigResponse {
+            collections: vec![(
+                "yt".into(),
+                CollectionConfig {
+                    dir: "/v".into(),
+                    command: "yt-dlp %".into(),
+                    domains: vec!["youtu.be".into()],
+                },
+            )],
+        });
+        assert_eq!(state.route_url("https://youtu.be/y"), Some("yt"));
+        assert_eq!(
+            state.download_for_url("https://youtu.be/x").map(|d| d.id),
+            Some(4)
+        );
+        assert!(state.download_for_url("https://youtu.be/y").is_none());
+    }
+
+    #[test]
+    fn test_command_for() {
+        let collection = CollectionConfig {
+            dir: "/tmp".into(),
+            command: "gallery-dl % --filter 50%".into(),
+            domains: vec![],
+        };
+        // Only the first `%` is substituted, matching the server
+        assert_eq!(
+            collection.command_for("https://example.com/g/1"),
+            "gallery-dl https://example.com/g/1 --filter 50%"
+        );
+    }
+
+    #[test]
+    fn test_parse_url_list() {
+        assert_eq!(
+            parse_url_list("https://a.example/1, https://a.example/2\n\n  https://b.example\r\n"),
+            vec![
+                "https://a.example/1",
+                "https://a.example/2",
+                "https://b.example"
+            ]
+        );
+        assert!(parse_url_list(" \n,\n").is_empty());
+    }
+
+    #[test]
+    fn test_is_web_url() {
+        assert!(is_web_url("https://youtube.com/watch?v=x"));
+        assert!(is_web_url("HTTP://example.com"));
+        assert!(!is_web_url("ftp://example.com/file"));
+        assert!(!is_web_url("example.com/no-scheme"));
+        assert!(!is_web_url("https://"));
+        assert!(!is_web_url("not a url"));
+    }
+
+    #[test]
+    fn test_sort_by_time() {
+        // Ids out of time order, as after a backup is restored
+        let mut state = AppState {
             downloads: vec![
                 Download {
-                    id: 1,
-                    status: "success".into(),
-                    ..Default::default()
+                    created_at: Timestamp::parse("2026-10-03T00:00:00Z"),
+                    ..download(1, "pending")
                 },
                 Download {
-                    id: 3,
-                    status: "success".into(),
-                    ..Default::default()
+                    created_at: Timestamp::parse("2026-10-01T00:00:00Z"),
+                    downloaded_at: Timestamp::parse("2026-10-02T00:00:00Z"),
+                    ..download(3, "success")
                 },
                 Download {
-                    id: 2,
-                    status: "success".into(),
-                    ..Default::default()
+                    created_at: Timestamp::parse("2026-10-02T00:00:00Z"),
+                    downloaded_at: Timestamp::parse("2026-10-04T00:00:00Z"),
+                    ..download(2, "success")
                 },
             ],
-            sort_order: SortOrder::NewestFirst,
+            sort_order: SortOrder::new(SortKey::Created),
             ..Default::default()
         };
-        let filtered = state.filtered_downloads();
-        assert_eq!(filtered[0].id, 3);
-        assert_eq!(filtered[1].id, 2);
-        assert_eq!(filtered[2].id, 1);
+        let ids = |state: &AppState| -> Vec<i64> {
+            state.filtered_downloads().iter().map(|d| d.id).collect()
+        };
+        assert_eq!(ids(&state), vec![1, 2, 3]);
+        state.sort_order = state.sort_order.reversed();
+        assert_eq!(ids(&state), vec![3, 2, 1]);
+        // Unfinished downloads last in either direction
+        state.sort_order = SortOrder::new(SortKey::Downloaded);
+        assert_eq!(ids(&state), vec![2, 3, 1]);
+        state.sort_order = state.sort_order.reversed();
+        assert_eq!(ids(&state), vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn test_sort_by_title_status_priority_domain() {
+        let mut state = AppState {
+            downloads: vec![
+                Download {
+                    title: Some("beta".into()),
+                    url: "https://b.example.com/1".into(),
+                    ..download(1, "error")
+                },
+                Download {
+                    priority: "high".into(),
+                    url: "not a url".into(),
+                    ..download(2, "success")
+                },
+                Download {
+                    title: Some("alpha".into()),
+                    priority: "weird".into(),
+                    url: "https://a.example.com/3".into(),
+                    ..download(3, "pending")
+                },
+                Download {
+                    title: Some("  ".into()),
+                    priority: "high".into(),
+                    url: "https://c.example.com/4".into(),
+                    ..download(4, "pending")
+                },
+            ],
+            ..Default::default()
+        };
+        let mut ids = |order: SortOrder| -> Vec<i64> {
+            state.sort_order = order;
+            state.filtered_downloads().iter().map(|d| d.id).collect()
+        };
+        // Untitled last either way, ties newest first
+        assert_eq!(ids(SortOrder::new(SortKey::Title)), vec![3, 1, 4, 2]);
+        assert_eq!(
+            ids(SortOrder::new(SortKey::Title).reversed()),
+            vec![1, 3, 4, 2]
+        );
+        assert_eq!(ids(SortOrder::new(SortKey::Status)), vec![4, 3, 2, 1]);
+        assert_eq!(ids(SortOrder::new(SortKey::Priority)), vec![4, 2, 1, 3]);
+        assert_eq!(ids(SortOrder::new(SortKey::Domain)), vec![3, 1, 4, 2]);
+        assert_eq!(
+            ids(SortOrder::new(SortKey::Domain).reversed()),
+            vec![4, 1, 3, 2]
+        );
+    }
+
+    #[test]
+    fn test_grouped_downloads() {
+        let mut state = AppState {
+            downloads: vec![
+                Download {
+                    url: "https://www.reddit.com/r/1".into(),
+                    created_at: Some(Timestamp(1)),
+                    ..download(1, "error")
+                },
+                Download {
+                    url: "https://news.bbc.co.uk/2".into(),
+                    created_at: Some(Timestamp(2)),
+                    ..download(2, "success")
+                },
+                Download {
+                    url: "not a url".into(),
+                    created_at: Some(Timestamp(3)),
+                    ..download(3, "error")
+                },
+                Download {
+                    url: "https://old.reddit.com/r/4".into(),
+                    created_at: Some(Timestamp(4)),
+                    ..download(4, "pending")
+                },
+            ],
+            ..Default::default()
+        };
+        let groups = |state: &AppState| -> Vec<(String, Vec<i64>)> {
+            state
+                .grouped_downloads()
+                .iter()
+                .map(|g| (g.title.clone(), g.downloads.iter().map(|d| d.id).collect()))
+                .collect()
+        };
+        assert_eq!(groups(&state), vec![(String::new(), vec![4, 3, 2, 1])]);
+
+        // By site name, with downloads lacking a value last; sort kept
+        // within groups
+        state.group_by = GroupBy::Domain;
+        assert_eq!(
+            groups(&state),
+            vec![
+                ("bbc.co.uk".into(), vec![2]),
+                ("reddit.com".into(), vec![4, 1]),
+                ("No domain".into(), vec![3]),
+            ]
+        );
+        for (host, site) in [
+            ("www.example.com", "example.com"),
+            ("example.com", "example.com"),
+            ("a.b.example.com.au", "example.com.au"),
+            ("localhost", "localhost"),
+            ("192.168.1.10", "192.168.1.10"),
+        ] {
+            assert_eq!(registrable_domain(host), site);
+        }
+
+        state.group_by = GroupBy::Status;
+        state.sort_order = state.sort_order.reversed();
+        assert_eq!(
+            groups(&state),
+            vec![
+                ("Pending".into(), vec![4]),
+                ("Success".into(), vec![2]),
+                ("Error".into(), vec![1, 3]),
+            ]
+        );
+
+        let errors = &state.grouped_downloads()[2];
+        let header = errors.header(true);
+        assert_eq!((header.key.as_str(), header.count), ("error", 2));
+        assert!(header.collapsed);
+        assert!(header.action_counts.contains(&(DownloadAction::Retry, 2)));
+        assert!(header.action_counts.contains(&(DownloadAction::Reset, 0)));
+    }
+
+    #[test]
+    fn test_sort_order_names() {
+        let order = SortOrder::new(SortKey::Title).reversed();
+        let json = serde_json::to_string(&order).unwrap();
+        assert_eq!(json, "\"title-desc\"");
+        assert_eq!(serde_json::from_str::<SortOrder>(&json).unwrap(), order);
+        let parse = |name: &str| SortOrder::from(name.to_string());
+        assert_eq!(parse("priority"), SortOrder::new(SortKey::Priority));
+        assert_eq!(parse("bogus-asc"), SortOrder::default());
+    }
+
+    #[test]
+    fn test_time_in_queue() {
+        let mut dl = Download {
+            status: "success".into(),
+            created_at: Some(Timestamp(1_000)),
+            downloaded_at: Some(Timestamp(61_000)),
+            ..Default::default()
+        };
+        assert_eq!(dl.time_in_queue(), Some(60_000));
+        dl.status = "error".into();
+        assert_eq!(dl.time_in_queue(), None);
     }
 
     #[test]
@@ -489,7 +2338,7 @@ mod tests {
                     ..Default::default()
                 },
             ],
-            sort_order: SortOrder::Collection,
+            sort_order: SortOrder::new(SortKey::Collection),
             ..Default::default()
         };
         let filtered = state.filtered_downloads();
@@ -497,6 +2346,132 @@ mod tests {
         assert_eq!(filtered[1].collection, "zt");
     }
 
+    #[test]
+    fn test_refresh_data_failed() {
+        let data = RefreshData::failed(ApiError::Timeout);
+        assert_eq!(data.failed_endpoints(), Endpoint::ALL);
+        assert!(RefreshData::default().failed_endpoints().is_empty());
+    }
+
+    #[test]
+    fn test_record_refresh_connection_status() {
+        let mut state = AppState::default();
+        assert_eq!(state.connection, ConnectionStatus::Connecting);
+
+        state.record_refresh(vec![], SystemTime::UNIX_EPOCH);
+        assert_eq!(state.connection, ConnectionStatus::Connected);
+
+        state.record_refresh(vec![Endpoint::Logs], SystemTime::UNIX_EPOCH);
+        assert_eq!(
+            state.connection,
+            ConnectionStatus::Degraded(vec![Endpoint::Logs])
+        );
+
+        let first = SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(10);
+        let later = first + std::time::Duration::from_secs(30);
+        state.record_refresh(Endpoint::ALL.to_vec(), first);
+        state.record_refresh(Endpoint::ALL.to_vec(), later);
+        assert_eq!(
+            state.connection,
+            ConnectionStatus::Unreachable { since: first }
+        );
+        assert_eq!(state.failed_refreshes, 2);
+    }
+
+    #[test]
+    fn test_refresh_backoff() {
+        let mut state = AppState::default();
+        assert!(state.tick());
+
+        for _ in 0..3 {
+            state.record_refresh(Endpoint::ALL.to_vec(), SystemTime::UNIX_EPOCH);
+        }
+        assert_eq!(state.backoff_ticks(), 8);
+        let due: Vec<bool> = (0..8).map(|_| state.tick()).collect();
+        assert_eq!(due.iter().filter(|d| **d).count(), 1);
+        assert!(due[7]);
+
+        for _ in 0..10 {
+            state.record_refresh(Endpoint::ALL.to_vec(), SystemTime::UNIX_EPOCH);
+        }
+        assert_eq!(state.backoff_ticks(), 16);
+
+        state.reset_backoff();
+        assert!(state.tick());
+        state.record_refresh(vec![], SystemTime::UNIX_EPOCH);
+        assert_eq!(state.backoff_ticks(), 1);
+    }
+
+    #[test]
+    fn test_stream_slows_polling() {
+        let mut state = AppState {
+            stream: StreamStatus::Live,
+            ..Default::default()
+        };
+        assert_eq!(state.backoff_ticks(), STREAM_RESYNC_TICKS);
+        state.stream = StreamStatus::Unsupported;
+        assert_eq!(state.backoff_ticks(), 1);
+    }
+
+    #[test]
+    fn test_background_slows_polling() {
+        let mut state = AppState {
+            background: true,
+            ..Default::default()
+        };
+        assert_eq!(state.backoff_ticks(), BACKGROUND_TICK_FACTOR);
+        state.stream = StreamStatus::Live;
+        assert_eq!(
+            state.backoff_ticks(),
+            STREAM_RESYNC_TICKS * BACKGROUND_TICK_FACTOR
+        );
+    }
+
+    #[test]
+    fn test_apply_event_upserts_and_recounts() {
+        let mut state = AppState {
+            downloads: vec![Download {
+                id: 1,
+                status: "pending".into(),
+                ..Default::default()
+            }],
+            counts: vec![StatusCount {
+                status: "pending".into(),
+                count: 1,
+            }],
+            ..Default::default()
+        };
+
+        state.apply_event(ServerEvent::Download(Download {
+            id: 1,
+            status: "downloading".into(),
+            ..Default::default()
+        }));
+        state.apply_event(ServerEvent::Download(Download {
+            id: 2,
+            status: "pending".into(),
+            ..Default::default()
+        }));
+        assert_eq!(state.downloads.len(), 2);
+        assert_eq!(state.downloads[0].status, DownloadStatus::Downloading);
+        assert_eq!(state.count_for(&DownloadStatus::Downloading), 1);
+        assert_eq!(state.count_for(&DownloadStatus::Pending), 1);
+
+        state.apply_event(ServerEvent::Deleted(1));
+        assert_eq!(state.downloads.len(), 1);
+        assert_eq!(state.count_for(&DownloadStatus::Downloading), 0);
+    }
+
+    #[test]
+    fn test_apply_event_log_lines_are_capped() {
+        let mut state = AppState::default();
+        for i in 0..MAX_LOG_LINES + 5 {
+            state.apply_event(ServerEvent::Log(format!("line {i}")));
+        }
+        assert_eq!(state.logs.len(), MAX_LOG_LINES);
+        assert_eq!(state.logs[0].text, "line 5");
+    }
+
     #[test]
     fn test_formatted_uptime() {
         let sys = SystemInfo {
@@ -536,6 +2511,73 @@ mod tests {
         let d: Download = serde_json::from_str(json).unwrap();
         assert_eq!(d.id, 1);
         assert_eq!(d.collection, "yt");
-        assert_eq!(d.status, "pending");
+        assert_eq!(d.status, DownloadStatus::Pending);
+        assert_eq!(d.priority, Priority::Normal);
+    }
+
+    #[test]
+    fn test_unreadable_times_keep_the_download() {
+        let json = r#"[
+            {"id": 1, "collection": "yt", "createdAt": null, "downloadedAt": "soon",
+             "priority": "normal", "status": "success", "title": null,
+             "url": "https://example.com/1", "errorMessage": null},
+            {"id": 2, "collection": "yt", "createdAt": "not a date",
+             "priority": "normal", "status": "pending", "title": null,
+             "url": "https://example.com/2", "errorMessage": null},
+            {"id": 3, "collection": "yt", "createdAt": "2026-10-01T12:00:00.000Z",
+             "downloadedAt": null, "priority": "normal", "status": "pending",
+             "title": null, "url": "https://example.com/3", "errorMessage": null}
+        ]"#;
+        let downloads: Vec<Download> = serde_json::from_str(json).unwrap();
+        assert_eq!(downloads.len(), 3);
+        assert_eq!(
+            (downloads[0].created_at, downloads[0].downloaded_at),
+            (None, None)
+        );
+        assert_eq!(downloads[1].created_at, None);
+        assert_eq!(downloads[0].time_in_queue(), None);
+
+        // Missing times sort last either way and match no date filter
+        let mut state = AppState {
+            downloads,
+            ..Default::default()
+        };
+        let ids = |state: &AppState| -> Vec<i64> {
+            state.filtered_downloads().iter().map(|d| d.id).collect()
+        };
+        assert_eq!(ids(&state), vec![3, 2, 1]);
+        state.sort_order = state.sort_order.reversed();
+        assert_eq!(ids(&state), vec![3, 2, 1]);
+        state.download_search = "after:2026-01-01".into();
+        assert_eq!(ids(&state), vec![3]);
+    }
+
+    #[test]
+    fn test_unknown_status_and_priority() {
+        let json = r#"{
+            "id": 2,
+            "collection": "yt",
+            "createdAt": "2024-01-01",
+            "downloadedAt": null,
+            "priority": "urgent",
+            "status": "paused",
+            "title": null,
+            "url": "https://example.com",
+            "errorMessage": null
+        }"#;
+        let d: Download = serde_json::from_str(json).unwrap();
+        assert_eq!(d.status, DownloadStatus::Unknown("paused".into()));
+        assert_eq!(d.priority, Priority::Unknown("urgent".into()));
+        assert_eq!(d.status.css_class(), "dim-label");
+
+        // Written back under the server's names
+        let value = serde_json::to_value(&d).unwrap();
+        assert_eq!(value["status"], "paused");
+        assert_eq!(value["priority"], "urgent");
+        let known = serde_json::to_value(DownloadStatus::Downloading).unwrap();
+        assert_eq!(known, "downloading");
+        for status in DownloadStatus::KNOWN {
+            assert_eq!(&DownloadStatus::from(status.name()), status);
+        }
     }
 }
@@ -0,0 +1,211 @@
+//! Checking URLs before they're added, so problems show up in the add dialog
+//! rather than as server errors afterwards.
+//!
+//! Each entry is judged against the current state: malformed URLs, URLs
+//! already in the download list, repeats within the same input, and URLs no
+//! collection's domains match (which the server would skip) are all left out
+//! of what gets submitted.
+
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+
+use crate::types::{AppState, Download, DownloadStatus, is_web_url};
+
+/// What adding one entry would do
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    /// Will be added, filed under this collection (unknown until the config loads)
+    Accepted(Option<String>),
+    /// Not an absolute http(s) URL
+    Invalid,
+    /// Already in the download list, with its status
+    AlreadyAdded(DownloadStatus),
+    /// Entered earlier in the same input
+    Repeated,
+    /// No collection's domains match, so the server would skip it
+    Unmatched,
+}
+
+/// One URL from the input and its verdict
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub url: String,
+    pub verdict: Verdict,
+    /// Line of the input the URL is on
+    pub line: usize,
+    /// Character range of the URL within its line
+    pub chars: Range<usize>,
+}
+
+impl Entry {
+    pub fn is_accepted(&self) -> bool {
+        matches!(self.verdict, Verdict::Accepted(_))
+    }
+}
+
+/// Check text entered one URL per line or comma-separated
+pub fn check_text(state: &AppState, text: &str) -> Vec<Entry> {
+    let mut pieces = Vec::new();
+    for (line, content) in text.split('\n').enumerate() {
+        let mut offset = 0;
+        for part in content.split(',') {
+            let leading = part.chars().take_while(|c| c.is_whitespace()).count();
+            let url = part.trim();
+            let start = offset + leading;
+            if !url.is_empty() {
+                pieces.push((url.to_string(), line, start..start + url.chars().count()));
+            }
+            // Skip past the part and its comma
+            offset += part.chars().count() + 1;
+        }
+    }
+    judge(state, pieces)
+}
+
+/// Check URLs that are already split, e.g. from a `dlm://` link
+pub fn check_urls(state: &AppState, urls: &[String]) -> Vec<Entry> {
+    let pieces = urls
+        .iter()
+        .enumerate()
+        .map(|(line, url)| (url.clone(), line, 0..url.chars().count()))
+        .collect();
+    judge(state, pieces)
+}
+
+fn judge(state: &AppState, pieces: Vec<(String, usize, Range<usize>)>) -> Vec<Entry> {
+    // Imports can check thousands of URLs against thousands of downloads
+    let mut by_url: HashMap<&str, &Download> = HashMap::with_capacity(state.downloads.len());
+    for dl in &state.downloads {
+        by_url.entry(dl.url.as_str()).or_insert(dl);
+    }
+    let mut seen: HashSet<String> = HashSet::new();
+    pieces
+        .into_iter()
+        .map(|(url, line, chars)| {
+            let verdict = if !is_web_url(&url) {
+                Verdict::Invalid
+            } else if seen.contains(&url) {
+                Verdict::Repeated
+            } else if let Some(existing) = by_url.get(url.as_str()) {
+                Verdict::AlreadyAdded(existing.status.clone())
+            } else if state.config.is_none() {
+                Verdict::Accepted(None)
+            } else {
+                match state.route_url(&url) {
+                    Some(collection) => Verdict::Accepted(Some(collection.to_string())),
+                    None => Verdict::Unmatched,
+                }
+            };
+            seen.insert(url.clone());
+            Entry {
+                url,
+                verdict,
+                line,
+                chars,
+            }
+        })
+        .collect()
+}
+
+/// The URLs that will be submitted
+pub fn accepted(entries: &[Entry]) -> Vec<String> {
+    entries
+        .iter()
+        .filter(|e| e.is_accepted())
+        .map(|e| e.url.clone())
+        .collect()
+}
+
+/// One-line tally, e.g. "2 to add · 1 invalid · 1 already added"
+pub fn summary(entries: &[Entry]) -> String {
+    let count = |f: fn(&Verdict) -> bool| entries.iter().filter(|e| f(&e.verdict)).count();
+    let parts = [
+        (count(|v| matches!(v, Verdict::Accepted(_))), "to add"),
+        (count(|v| *v == Verdict::Invalid), "invalid"),
+        (
+            count(|v| matches!(v, Verdict::AlreadyAdded(_))),
+            "already added",
+        ),
+        (count(|v| *v == Verdict::Repeated), "repeated"),
+        (count(|v| *v == Verdict::Unmatched), "with no collection"),
+    ];
+    parts
+        .iter()
+        .enumerate()
+        // Always show how many will be added
+        .filter(|(i, (n, _))| *i == 0 || *n > 0)
+        .map(|(_, (n, what))| format!("{n} {what}"))
+        .collect::<Vec<_>>()
+        .join(" · ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{CollectionConfig, ConfigResponse, Download};
+
+    fn state() -> AppState {
+        AppState {
+            config: Some(ConfigResponse {
+                collections: vec![(
+                    "yt".into(),
+                    CollectionConfig {
+                        dir: "/v".into(),
+                        command: "yt-dlp %".into(),
+                        domains: vec!["youtube.com".into()],
+                    },
+                )],
+            }),
+            downloads: vec![Download {
+                id: 1,
+                url: "https://youtube.com/watch?v=old".into(),
+                status: "success".into(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_check_text_verdicts() {
+        let text = "https://youtube.com/watch?v=new\n\
+                    not a url, https://youtube.com/watch?v=old\n\
+                    https://example.com/a\n\
+                    https://youtube.com/watch?v=new";
+        let entries = check_text(&state(), text);
+        let verdicts: Vec<&Verdict> = entries.iter().map(|e| &e.verdict).collect();
+        assert_eq!(
+            verdicts,
+            vec![
+                &Verdict::Accepted(Some("yt".into())),
+                &Verdict::Invalid,
+                &Verdict::AlreadyAdded("success".into()),
+                &Verdict::Unmatched,
+                &Verdict::Repeated,
+            ]
+        );
+        assert_eq!(accepted(&entries), vec!["https://youtube.com/watch?v=new"]);
+        assert_eq!(
+            summary(&entries),
+            "1 to add · 1 invalid · 1 already added · 1 repeated · 1 with no collection"
+        );
+    }
+
+    #[test]
+    fn test_check_text_positions() {
+        let entries = check_text(&state(), "\n  bad ,  https://youtube.com/x\r\n");
+        assert_eq!(entries.len(), 2);
+        assert_eq!((entries[0].line, entries[0].chars.clone()), (1, 2..5));
+        assert_eq!(entries[1].line, 1);
+        assert_eq!(entries[1].chars, 9..9 + "https://youtube.com/x".len());
+    }
+
+    #[test]
+    fn test_check_without_config() {
+        let state = AppState::default();
+        let entries = check_urls(&state, &["https://example.com/a".to_string()]);
+        assert_eq!(entries[0].verdict, Verdict::Accepted(None));
+        assert_eq!(summary(&entries), "1 to add");
+        assert_eq!(summary(&[]), "0 to add");
+    }
+}